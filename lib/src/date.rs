@@ -0,0 +1,86 @@
+const SECONDS_PER_DAY: i64 = 86400;
+const SECONDS_PER_HOUR: i64 = 3600;
+
+/// UTC day of a unix timestamp, formatted as `YYYY-MM-DD`.
+pub fn utc_day(timestamp_seconds: i64) -> String {
+    let (year, month, day) = civil_from_days(timestamp_seconds.div_euclid(SECONDS_PER_DAY));
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// UTC hour of a unix timestamp, formatted as `YYYY-MM-DDTHH`.
+pub fn utc_hour(timestamp_seconds: i64) -> String {
+    let hour = timestamp_seconds.rem_euclid(SECONDS_PER_DAY) / SECONDS_PER_HOUR;
+    format!("{}T{:02}", utc_day(timestamp_seconds), hour)
+}
+
+// Converts days since 1970-01-01 to a (year, month, day) proleptic Gregorian date, see
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month as u32, day as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_the_unix_epoch() {
+        assert_eq!(utc_day(0), "1970-01-01");
+        assert_eq!(utc_hour(0), "1970-01-01T00");
+        assert_eq!(utc_hour(-1), "1969-12-31T23");
+    }
+
+    #[test]
+    fn rolls_over_leap_days() {
+        let leap_day = 1582934400;
+        assert_eq!(utc_day(leap_day - 1), "2020-02-28");
+        assert_eq!(utc_day(leap_day), "2020-02-29");
+        assert_eq!(utc_day(leap_day + SECONDS_PER_DAY), "2020-03-01");
+
+        // Centuries are leap years only when divisible by 400
+        assert_eq!(utc_day(951782400), "2000-02-29");
+        assert_eq!(utc_day(4107456000), "2100-02-28");
+        assert_eq!(utc_day(4107456000 + SECONDS_PER_DAY), "2100-03-01");
+    }
+
+    #[test]
+    fn rolls_over_the_year_end() {
+        let new_year = 1609459200;
+        assert_eq!(utc_day(new_year - 1), "2020-12-31");
+        assert_eq!(utc_hour(new_year - 1), "2020-12-31T23");
+        assert_eq!(utc_day(new_year), "2021-01-01");
+        assert_eq!(utc_hour(new_year), "2021-01-01T00");
+    }
+
+    #[test]
+    fn rolls_over_hours() {
+        let day = 1609372800;
+        assert_eq!(utc_hour(day + 3599), "2020-12-31T00");
+        assert_eq!(utc_hour(day + 3600), "2020-12-31T01");
+        assert_eq!(utc_hour(day + SECONDS_PER_DAY - 3601), "2020-12-31T22");
+        assert_eq!(utc_hour(day + SECONDS_PER_DAY - 3600), "2020-12-31T23");
+    }
+
+    #[test]
+    fn converts_days_to_civil_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(-719468), (0, 3, 1));
+        assert_eq!(civil_from_days(18321), (2020, 2, 29));
+    }
+}
@@ -0,0 +1,13 @@
+//! Consumer library for the `uniswap_pricing` substreams package.
+//!
+//! Exposes the protobuf types and typed helpers to read prices from the package stores, so
+//! importing substreams never hand-write keys such as `UsdPriceByTokenSymbol:GRT`. `quote`
+//! emulates `UniswapV2Library.getAmountsOut` over the stored pair reserves, and
+//! `store_key_manager::StoreKey` builds the key of any other store value.
+
+pub mod date;
+pub mod pb;
+pub mod prices;
+pub mod quote;
+pub mod store_key_manager;
+mod token;
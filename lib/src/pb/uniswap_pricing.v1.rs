@@ -0,0 +1,485 @@
+// @generated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FactoryEvents {
+    #[prost(message, repeated, tag="1")]
+    pub pair_createds: ::prost::alloc::vec::Vec<PairCreated>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PairCreated {
+    #[prost(string, tag="1")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub block_index: u32,
+    #[prost(message, optional, tag="3")]
+    pub block_time: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint64, tag="4")]
+    pub block_number: u64,
+    #[prost(uint64, tag="5")]
+    pub ordinal: u64,
+    #[prost(message, optional, tag="6")]
+    pub token0: ::core::option::Option<Erc20Token>,
+    #[prost(message, optional, tag="7")]
+    pub token1: ::core::option::Option<Erc20Token>,
+    #[prost(string, tag="8")]
+    pub pair_address: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub factory: ::prost::alloc::string::String,
+    /// Sender of the transaction that created the pair
+    #[prost(string, tag="10")]
+    pub tx_from: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Erc20Tokens {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<Erc20Token>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Erc20Token {
+    #[prost(string, tag="1")]
+    pub address: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(uint64, tag="4")]
+    pub decimals: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Erc20Price {
+    #[prost(message, optional, tag="1")]
+    pub token: ::core::option::Option<Erc20Token>,
+    /// Empty when priced against WETH before any ETH price is known, `price_eth` is still set
+    #[prost(string, tag="2")]
+    pub price_usd: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub block_number: u64,
+    #[prost(uint64, tag="4")]
+    pub ordinal: u64,
+    #[prost(enumeration="erc20_price::Source", tag="5")]
+    pub source: i32,
+    #[prost(message, optional, tag="6")]
+    pub block_time: ::core::option::Option<::prost_types::Timestamp>,
+    /// Price denominated in ETH. Empty when priced against a stablecoin before any ETH price is
+    /// known.
+    #[prost(string, tag="7")]
+    pub price_eth: ::prost::alloc::string::String,
+    /// Pair the price was derived from. Empty for prices not derived from a Uniswap pool, i.e.
+    /// `CHAINLINK` and `REFERENCE` prices.
+    #[prost(string, tag="8")]
+    pub pair_address: ::prost::alloc::string::String,
+    /// Source of the ETH/USD price used to derive `price_usd` (WETH pairs) or `price_eth`. `UNKNOWN`
+    /// when no ETH price was known, the derived field is then empty.
+    #[prost(enumeration="erc20_price::Source", tag="9")]
+    pub eth_price_source: i32,
+}
+/// Nested message and enum types in `Erc20Price`.
+pub mod erc20_price {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Source {
+        Oracles = 0,
+        Chainlink = 1,
+        Uniswap = 2,
+        /// No price was known, only used for `eth_price_source`
+        Unknown = 3,
+        /// Valued at its reference price rather than from a pool: WETH at the ETH price, a stablecoin
+        /// at its base price when its pools give it no USD price
+        Reference = 4,
+    }
+    impl Source {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Source::Oracles => "ORACLES",
+                Source::Chainlink => "CHAINLINK",
+                Source::Uniswap => "UNISWAP",
+                Source::Unknown => "UNKNOWN",
+                Source::Reference => "REFERENCE",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "ORACLES" => Some(Self::Oracles),
+                "CHAINLINK" => Some(Self::Chainlink),
+                "UNISWAP" => Some(Self::Uniswap),
+                "UNKNOWN" => Some(Self::Unknown),
+                "REFERENCE" => Some(Self::Reference),
+                _ => None,
+            }
+        }
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Erc20Prices {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<Erc20Price>,
+}
+/// A stablecoin of the pricing set, valued at the end of the block
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StableCoinPrice {
+    #[prost(string, tag="1")]
+    pub address: ::prost::alloc::string::String,
+    /// Unset when the coin must not be used to price other tokens, e.g. an algorithmic coin without
+    /// a fresh Chainlink answer
+    #[prost(string, tag="2")]
+    pub price_usd: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StableCoinPrices {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<StableCoinPrice>,
+}
+/// Prices from the Chainlink USD feeds, with the pricing mode resolved from the `pricing_mode` param
+/// of `map_chainlink_prices`
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChainlinkPrices {
+    /// Empty unless `chainlink_first`
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<Erc20Price>,
+    #[prost(bool, tag="2")]
+    pub chainlink_first: bool,
+    /// Addresses of the verified tokens whose Chainlink USD answer is within the heartbeat at the end
+    /// of the block, and takes precedence over their Uniswap price. Empty unless `chainlink_first`
+    #[prost(string, repeated, tag="3")]
+    pub chainlink_priced_tokens: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenLiquidity {
+    #[prost(message, optional, tag="1")]
+    pub token: ::core::option::Option<Erc20Token>,
+    #[prost(string, tag="2")]
+    pub pair_address: ::prost::alloc::string::String,
+    /// USD value of both sides of the pair at the end of the block, derived from the WETH or
+    /// stablecoin reserve
+    #[prost(string, tag="3")]
+    pub liquidity_usd: ::prost::alloc::string::String,
+    #[prost(uint64, tag="4")]
+    pub ordinal: u64,
+    #[prost(uint64, tag="5")]
+    pub block_number: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenLiquidities {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<TokenLiquidity>,
+    /// Symbol takeover settings, parsed from the `map_token_liquidity` params once for every
+    /// downstream module
+    #[prost(string, tag="2")]
+    pub min_liquidity_usd: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub takeover_margin_percent: ::prost::alloc::string::String,
+    #[prost(uint64, tag="4")]
+    pub takeover_blocks: u64,
+}
+/// Token of a symbol whose pair liquidity leads every other token of that symbol by the takeover
+/// margin, evaluated whenever one of the symbol's pairs is updated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SymbolLeader {
+    #[prost(string, tag="1")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Unset when no token leads by the margin
+    #[prost(message, optional, tag="2")]
+    pub leader: ::core::option::Option<Erc20Token>,
+    /// Addresses of the symbol's other tokens, whose lead, if any, is over
+    #[prost(string, repeated, tag="3")]
+    pub other_tokens: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint64, tag="4")]
+    pub block_number: u64,
+    #[prost(uint64, tag="5")]
+    pub ordinal: u64,
+    /// Blocks a lead must last before the leader takes the symbol over
+    #[prost(uint64, tag="6")]
+    pub takeover_blocks: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SymbolLeaders {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<SymbolLeader>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuotePrice {
+    #[prost(message, optional, tag="1")]
+    pub token: ::core::option::Option<Erc20Token>,
+    /// Symbol of the quote currency, e.g. EUR or BTC
+    #[prost(string, tag="2")]
+    pub quote_currency: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub price: ::prost::alloc::string::String,
+    #[prost(uint64, tag="4")]
+    pub block_number: u64,
+    #[prost(uint64, tag="5")]
+    pub ordinal: u64,
+    #[prost(message, optional, tag="6")]
+    pub block_time: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuotePrices {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<QuotePrice>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PriceDeviation {
+    #[prost(message, optional, tag="1")]
+    pub token: ::core::option::Option<Erc20Token>,
+    #[prost(string, tag="2")]
+    pub pair_address: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub uniswap_price_usd: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub chainlink_price_usd: ::prost::alloc::string::String,
+    /// (uniswap - chainlink) / chainlink * 100
+    #[prost(string, tag="5")]
+    pub deviation_percent: ::prost::alloc::string::String,
+    /// Timestamp of the Chainlink round the Uniswap price is compared against
+    #[prost(int64, tag="6")]
+    pub chainlink_timestamp: i64,
+    #[prost(uint64, tag="7")]
+    pub block_number: u64,
+    #[prost(uint64, tag="8")]
+    pub ordinal: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PriceDeviations {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<PriceDeviation>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StalePrice {
+    /// Unset when the token was never priced
+    #[prost(message, optional, tag="1")]
+    pub last_price: ::core::option::Option<Erc20Price>,
+    /// Zero when the token was never priced
+    #[prost(uint64, tag="2")]
+    pub age_seconds: u64,
+    #[prost(string, tag="3")]
+    pub token_address: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StalePrices {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<StalePrice>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PriceChange {
+    #[prost(message, optional, tag="1")]
+    pub old_price: ::core::option::Option<Erc20Price>,
+    #[prost(message, optional, tag="2")]
+    pub new_price: ::core::option::Option<Erc20Price>,
+    /// (new - old) / old * 100
+    #[prost(string, tag="3")]
+    pub change_percent: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PriceChanges {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<PriceChange>,
+}
+/// USD amount that can be traded against a pair before its spot price moves by `percent`, from the
+/// constant product reserves and excluding the swap fee
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DepthLevel {
+    #[prost(uint32, tag="1")]
+    pub percent: u32,
+    /// Buying token0 with token1 until token0's price, in token1, is `percent` higher
+    #[prost(string, tag="2")]
+    pub up_usd: ::prost::alloc::string::String,
+    /// Selling token0 for token1 until token0's price, in token1, is `percent` lower
+    #[prost(string, tag="3")]
+    pub down_usd: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PairDepth {
+    #[prost(string, tag="1")]
+    pub pair_address: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="2")]
+    pub token0: ::core::option::Option<Erc20Token>,
+    #[prost(message, optional, tag="3")]
+    pub token1: ::core::option::Option<Erc20Token>,
+    /// USD value of each reserve, both are worth the same at the pair's spot price
+    #[prost(string, tag="4")]
+    pub reserve_usd: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="5")]
+    pub levels: ::prost::alloc::vec::Vec<DepthLevel>,
+    #[prost(uint64, tag="6")]
+    pub block_number: u64,
+    #[prost(uint64, tag="7")]
+    pub ordinal: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PairDepths {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<PairDepth>,
+}
+/// Reserves of a pair after one of its `Sync` events
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveSnapshot {
+    #[prost(string, tag="1")]
+    pub pair_address: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="2")]
+    pub token0: ::core::option::Option<Erc20Token>,
+    #[prost(message, optional, tag="3")]
+    pub token1: ::core::option::Option<Erc20Token>,
+    /// Raw reserves, in each token's smallest unit
+    #[prost(string, tag="4")]
+    pub reserve0: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub reserve1: ::prost::alloc::string::String,
+    #[prost(uint64, tag="6")]
+    pub block_number: u64,
+    #[prost(uint64, tag="7")]
+    pub ordinal: u64,
+    /// LP tokens the pair minted minus the ones it burnt since its previous `Sync`, in the LP token's
+    /// smallest unit. A pair syncs right after every mint and burn.
+    #[prost(string, tag="8")]
+    pub lp_supply_change: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveSnapshots {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<ReserveSnapshot>,
+}
+/// Reserves of a pair at the start of its current window on one of the two window grids, the second
+/// grid being offset by half a window
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LiquidityWindow {
+    #[prost(uint32, tag="1")]
+    pub grid: u32,
+    #[prost(message, optional, tag="2")]
+    pub start: ::core::option::Option<ReserveSnapshot>,
+    /// Ordinal of the `Sync` the pair entered the window on
+    #[prost(uint64, tag="3")]
+    pub ordinal: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LiquidityWindows {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<LiquidityWindow>,
+}
+/// A token's first Uniswap V2 pair receiving its first liquidity
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenLaunch {
+    #[prost(message, optional, tag="1")]
+    pub token: ::core::option::Option<Erc20Token>,
+    /// The token's first pair, with the transaction that created it and its sender
+    #[prost(message, optional, tag="2")]
+    pub pair: ::core::option::Option<PairCreated>,
+    /// Amounts of the first `Mint` of the pair, in whole tokens
+    #[prost(string, tag="3")]
+    pub token_amount: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub paired_token_amount: ::prost::alloc::string::String,
+    /// Twice the paired token amount at its latest price from `map_uniswap_prices` up to the `Mint`,
+    /// else at its end of block price in `store_uniswap_prices`. Empty when the paired token has no
+    /// price yet.
+    #[prost(string, tag="5")]
+    pub initial_liquidity_usd: ::prost::alloc::string::String,
+    /// First price `map_uniswap_prices` emitted for the token in the block of the first `Mint`. Only
+    /// set when the token is paired with WETH or a stablecoin.
+    #[prost(message, optional, tag="6")]
+    pub first_price: ::core::option::Option<Erc20Price>,
+    #[prost(string, tag="7")]
+    pub mint_tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag="8")]
+    pub block_number: u64,
+    #[prost(uint64, tag="9")]
+    pub ordinal: u64,
+    #[prost(message, optional, tag="10")]
+    pub block_time: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenLaunches {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<TokenLaunch>,
+}
+/// A pair losing a large share of its liquidity within a block or a short window
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LiquidityDrain {
+    #[prost(string, tag="1")]
+    pub pair_address: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="2")]
+    pub token0: ::core::option::Option<Erc20Token>,
+    #[prost(message, optional, tag="3")]
+    pub token1: ::core::option::Option<Erc20Token>,
+    /// Tokens of the pair that are neither WETH nor a stablecoin and are priced from this pair in
+    /// `store_uniswap_prices`, marked in `store_flagged_tokens`
+    #[prost(message, repeated, tag="4")]
+    pub flagged_tokens: ::prost::alloc::vec::Vec<Erc20Token>,
+    /// Share of the liquidity lost since the start of the block or window, in percent. Liquidity is
+    /// measured as sqrt(reserve0 * reserve1), which only adding or removing liquidity changes.
+    #[prost(string, tag="5")]
+    pub drop_percent: ::prost::alloc::string::String,
+    /// Reserves after the block, in whole tokens
+    #[prost(string, tag="6")]
+    pub reserve0: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub reserve1: ::prost::alloc::string::String,
+    /// Holder of the LP tokens burnt for the largest share of the pair's LP supply in the block, and
+    /// that share in percent. Empty when no liquidity was burnt in the block.
+    #[prost(string, tag="8")]
+    pub top_remover: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub top_remover_percent: ::prost::alloc::string::String,
+    #[prost(uint64, tag="10")]
+    pub block_number: u64,
+    #[prost(uint64, tag="11")]
+    pub ordinal: u64,
+    #[prost(message, optional, tag="12")]
+    pub block_time: ::core::option::Option<::prost_types::Timestamp>,
+    /// Share of the WETH or stablecoin reserve lost since the start of the block or window, in
+    /// percent, which selling the other token into the pair lowers as well. Empty when the pair holds
+    /// no such reserve or only such reserves.
+    #[prost(string, tag="13")]
+    pub quote_drop_percent: ::prost::alloc::string::String,
+    /// USD value of the WETH or stablecoin reserve lost, at the current price of that token. Empty
+    /// when `quote_drop_percent` is empty or that token has no price.
+    #[prost(string, tag="14")]
+    pub quote_drop_usd: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LiquidityDrains {
+    #[prost(message, repeated, tag="1")]
+    pub items: ::prost::alloc::vec::Vec<LiquidityDrain>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Warmup {
+    #[prost(bool, tag="1")]
+    pub is_warm: bool,
+}
+// @@protoc_insertion_point(module)
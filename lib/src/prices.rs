@@ -0,0 +1,149 @@
+use std::str::FromStr;
+
+use substreams::{scalar::BigDecimal, store::StoreGet};
+
+use crate::{
+    pb::uniswap_pricing::v1::{Erc20Price, LiquidityDrain, PairDepth, QuotePrice},
+    store_key_manager::StoreKey,
+};
+
+// Token addresses are accepted with or without the `0x` prefix and in any case, the stores key
+// them as lowercase hex without prefix.
+
+/// Latest USD price of a token, read from `store_uniswap_prices`.
+pub fn get_usd_price<S: StoreGet<Erc20Price>>(
+    store: &S,
+    token_address: &str,
+) -> Option<Erc20Price> {
+    let key = StoreKey::usd_price_by_address(&normalize_address(token_address));
+    store.get_last(key)
+}
+
+/// Latest USD price of a token by its canonical symbol, read from `store_uniswap_prices`.
+pub fn get_usd_price_by_symbol<S: StoreGet<Erc20Price>>(
+    store: &S,
+    token_symbol: &str,
+) -> Option<Erc20Price> {
+    store.get_last(StoreKey::usd_price_by_symbol(token_symbol))
+}
+
+/// Same as `get_usd_price`, returning only the parsed USD price.
+pub fn get_usd_price_bigdecimal<S: StoreGet<Erc20Price>>(
+    store: &S,
+    token_address: &str,
+) -> Option<BigDecimal> {
+    get_usd_price(store, token_address).and_then(|price| parse_price(&price.price_usd))
+}
+
+/// Same as `get_usd_price_by_symbol`, returning only the parsed USD price.
+pub fn get_usd_price_by_symbol_bigdecimal<S: StoreGet<Erc20Price>>(
+    store: &S,
+    token_symbol: &str,
+) -> Option<BigDecimal> {
+    get_usd_price_by_symbol(store, token_symbol).and_then(|price| parse_price(&price.price_usd))
+}
+
+/// Latest Uniswap derived ETH price in USD, read from `store_weth_prices`.
+pub fn get_eth_usd_price<S: StoreGet<Erc20Price>>(store: &S) -> Option<BigDecimal> {
+    store
+        .get_last(StoreKey::eth_usd_price_key())
+        .and_then(|price| parse_price(&price.price_usd))
+}
+
+/// Latest price of a token in one of the additional quote currencies, read from
+/// `store_quote_prices`.
+pub fn get_quote_price<S: StoreGet<QuotePrice>>(
+    store: &S,
+    token_address: &str,
+    quote_currency: &str,
+) -> Option<QuotePrice> {
+    let key = StoreKey::price_by_address(&normalize_address(token_address), quote_currency);
+    store.get_last(key)
+}
+
+/// Same as `get_quote_price`, returning only the parsed price.
+pub fn get_quote_price_bigdecimal<S: StoreGet<QuotePrice>>(
+    store: &S,
+    token_address: &str,
+    quote_currency: &str,
+) -> Option<BigDecimal> {
+    get_quote_price(store, token_address, quote_currency)
+        .and_then(|price| parse_price(&price.price))
+}
+
+/// Closing USD price of a token for a UTC day (`YYYY-MM-DD`), read from `store_historical_prices`.
+/// The value for the current day is the latest price so far.
+pub fn get_daily_close<S: StoreGet<Erc20Price>>(
+    store: &S,
+    token_address: &str,
+    day: &str,
+) -> Option<Erc20Price> {
+    let key = StoreKey::daily_price_by_address(&normalize_address(token_address), day);
+    store.get_last(key)
+}
+
+/// Closing USD price of a token for a UTC hour (`YYYY-MM-DDTHH`), read from
+/// `store_historical_prices`. The value for the current hour is the latest price so far.
+pub fn get_hourly_close<S: StoreGet<Erc20Price>>(
+    store: &S,
+    token_address: &str,
+    hour: &str,
+) -> Option<Erc20Price> {
+    let key = StoreKey::hourly_price_by_address(&normalize_address(token_address), hour);
+    store.get_last(key)
+}
+
+/// Latest liquidity drain of one of the token's pairs, read from `store_flagged_tokens`. `None`
+/// when the token was never flagged.
+pub fn get_liquidity_drain<S: StoreGet<LiquidityDrain>>(
+    store: &S,
+    token_address: &str,
+) -> Option<LiquidityDrain> {
+    store.get_last(StoreKey::flagged_token(&normalize_address(token_address)))
+}
+
+/// Same as `get_usd_price`, but `None` once the token has been flagged in `store_flagged_tokens`:
+/// its last price predates the drain, and any later one comes from a drained pool.
+pub fn get_trusted_usd_price<P, F>(
+    prices_store: &P,
+    flagged_store: &F,
+    token_address: &str,
+) -> Option<Erc20Price>
+where
+    P: StoreGet<Erc20Price>,
+    F: StoreGet<LiquidityDrain>,
+{
+    if get_liquidity_drain(flagged_store, token_address).is_some() {
+        return None;
+    }
+    get_usd_price(prices_store, token_address)
+}
+
+/// Latest depth of a pair, read from `store_pair_depth`.
+pub fn get_pair_depth<S: StoreGet<PairDepth>>(store: &S, pair_address: &str) -> Option<PairDepth> {
+    store.get_last(StoreKey::pair_depth(&normalize_address(pair_address)))
+}
+
+/// USD amount tradable against all pairs of a token before its price moves by `percent` (1, 2 or
+/// 5), up when `up` is set and down otherwise, read from `store_token_depth`.
+pub fn get_token_depth<S: StoreGet<BigDecimal>>(
+    store: &S,
+    token_address: &str,
+    percent: u32,
+    up: bool,
+) -> Option<BigDecimal> {
+    let direction = if up { "up" } else { "down" };
+    store.get_last(StoreKey::token_depth(
+        &normalize_address(token_address),
+        percent,
+        direction,
+    ))
+}
+
+pub(crate) fn normalize_address(address: &str) -> String {
+    address.trim_start_matches("0x").to_lowercase()
+}
+
+fn parse_price(price: &str) -> Option<BigDecimal> {
+    BigDecimal::from_str(price).ok()
+}
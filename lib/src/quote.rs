@@ -0,0 +1,90 @@
+use std::str::FromStr;
+
+use substreams::{scalar::BigInt, store::StoreGet};
+
+use crate::{
+    pb::uniswap_pricing::v1::ReserveSnapshot, prices::normalize_address,
+    store_key_manager::StoreKey,
+};
+
+// Amounts are raw, in each token's smallest unit, as in the Uniswap V2 contracts.
+
+/// Latest reserves of a pair, read from `store_pair_reserves`.
+pub fn get_pair_reserves<S: StoreGet<ReserveSnapshot>>(
+    store: &S,
+    pair_address: &str,
+) -> Option<ReserveSnapshot> {
+    store.get_last(StoreKey::pair_reserves(&normalize_address(pair_address)))
+}
+
+/// Latest reserves of the pair of two tokens, in either order, read from `store_pair_reserves`.
+pub fn get_pair_reserves_by_tokens<S: StoreGet<ReserveSnapshot>>(
+    store: &S,
+    token_a: &str,
+    token_b: &str,
+) -> Option<ReserveSnapshot> {
+    store.get_last(StoreKey::pair_reserves_by_tokens(
+        &normalize_address(token_a),
+        &normalize_address(token_b),
+    ))
+}
+
+/// Output of swapping `amount_in` against a pair holding `reserve_in` and `reserve_out`, with the
+/// 0.3% fee, as `UniswapV2Library.getAmountOut`. `None` where the library reverts, on an empty
+/// input or reserve.
+pub fn get_amount_out(
+    amount_in: &BigInt,
+    reserve_in: &BigInt,
+    reserve_out: &BigInt,
+) -> Option<BigInt> {
+    let zero = BigInt::zero();
+    if amount_in <= &zero || reserve_in <= &zero || reserve_out <= &zero {
+        return None;
+    }
+
+    let amount_in_with_fee = amount_in.clone() * BigInt::from(997);
+    let numerator = amount_in_with_fee.clone() * reserve_out.clone();
+    let denominator = reserve_in.clone() * BigInt::from(1000) + amount_in_with_fee;
+    Some(numerator / denominator)
+}
+
+/// Amounts out of swapping `amount_in` of `path[0]` hop by hop along `path`, through the pair of
+/// each two consecutive tokens, as `UniswapV2Library.getAmountsOut`. The first amount is
+/// `amount_in` and the last the amount of the final token received. Reserves are the latest ones
+/// in `store_pair_reserves`, so the quote ignores anything else traded in the same block.
+/// `None` when the path holds fewer than two tokens, or a pair on it is unknown or empty.
+pub fn get_amounts_out<S: StoreGet<ReserveSnapshot>>(
+    store: &S,
+    amount_in: &BigInt,
+    path: &[&str],
+) -> Option<Vec<BigInt>> {
+    if path.len() < 2 {
+        return None;
+    }
+
+    let mut amounts = vec![amount_in.clone()];
+    for hop in path.windows(2) {
+        let (reserve_in, reserve_out) = reserves_for(store, hop[0], hop[1])?;
+        let amount_out = get_amount_out(amounts.last()?, &reserve_in, &reserve_out)?;
+        amounts.push(amount_out);
+    }
+
+    Some(amounts)
+}
+
+// Reserves of the pair of `token_in` and `token_out`, in swap order
+fn reserves_for<S: StoreGet<ReserveSnapshot>>(
+    store: &S,
+    token_in: &str,
+    token_out: &str,
+) -> Option<(BigInt, BigInt)> {
+    let reserves = get_pair_reserves_by_tokens(store, token_in, token_out)?;
+    let reserve0 = BigInt::from_str(&reserves.reserve0).ok()?;
+    let reserve1 = BigInt::from_str(&reserves.reserve1).ok()?;
+
+    if reserves.token0_ref().address == normalize_address(token_in) {
+        Some((reserve0, reserve1))
+    } else {
+        Some((reserve1, reserve0))
+    }
+}
@@ -0,0 +1,235 @@
+// Key layout of every store, only reachable through the typed getters of `StoreKey`
+enum Key {
+    Pair(String),
+    Token(String),
+    EthUsdPrice,
+    UsdPriceByTokenAddress(String),
+    UsdPriceByTokenSymbol(String),
+    PriceByTokenAddress(String, String),
+    PriceByTokenSymbol(String, String),
+    LastUpdatedByTokenAddress(String),
+    PairLiquidity(String),
+    LiquidPairsBySymbol(String),
+    SymbolLeadSince(String),
+    CanonicalTokenBySymbol(String),
+    ChainlinkAnswer(String, String),
+    DailyPriceByTokenAddress(String, String),
+    HourlyPriceByTokenAddress(String, String),
+    PairDepth(String),
+    TokenDepth(String, u32, String),
+    PairReserves(String),
+    PairReservesByTokens(String, String),
+    FirstPairByToken(String),
+    LiquidityWindow(String, u32),
+    LpSupply(String),
+    FlaggedToken(String),
+    ChainlinkEthPrice,
+    ChainlinkPrice(String, String),
+}
+
+/// Keys of the package stores. Prefer the typed readers of `prices` and `quote` where they cover
+/// the value.
+pub struct StoreKey;
+
+impl StoreKey {
+    /// `PairCreated` event of a pair in `store_pair_created_events`.
+    pub fn pair_key(pair_address: &str) -> String {
+        Key::Pair(pair_address.to_string()).to_key_string()
+    }
+
+    /// `Erc20Token` metadata in `store_tokens`.
+    pub fn token_key(token_address: &str) -> String {
+        Key::Token(token_address.to_string()).to_key_string()
+    }
+
+    /// Uniswap derived ETH price in `store_weth_prices`.
+    pub fn eth_usd_price_key() -> String {
+        Key::EthUsdPrice.to_key_string()
+    }
+
+    /// Latest USD price of a token in `store_uniswap_prices`.
+    pub fn usd_price_by_address(token_address: &str) -> String {
+        Key::UsdPriceByTokenAddress(token_address.to_string()).to_key_string()
+    }
+
+    /// Latest USD price of the canonical token of a symbol in `store_uniswap_prices`.
+    pub fn usd_price_by_symbol(token_symbol: &str) -> String {
+        Key::UsdPriceByTokenSymbol(token_symbol.to_string()).to_key_string()
+    }
+
+    /// Latest price of a token in a quote currency in `store_quote_prices`.
+    pub fn price_by_address(token_address: &str, quote_currency: &str) -> String {
+        Key::PriceByTokenAddress(token_address.to_string(), quote_currency.to_string())
+            .to_key_string()
+    }
+
+    /// Latest price of the canonical token of a symbol in a quote currency in `store_quote_prices`.
+    pub fn price_by_symbol(token_symbol: &str, quote_currency: &str) -> String {
+        Key::PriceByTokenSymbol(token_symbol.to_string(), quote_currency.to_string())
+            .to_key_string()
+    }
+
+    /// Unix seconds of the latest price of a token in `store_price_last_updated`.
+    pub fn last_updated_by_address(token_address: &str) -> String {
+        Key::LastUpdatedByTokenAddress(token_address.to_string()).to_key_string()
+    }
+
+    /// Latest liquidity of a pair against WETH or a stablecoin in `store_pair_liquidity`.
+    pub fn pair_liquidity(pair_address: &str) -> String {
+        Key::PairLiquidity(pair_address.to_string()).to_key_string()
+    }
+
+    /// Pairs of a symbol that reached `min_liquidity_usd` in `store_symbol_pairs`, with repeats.
+    pub fn liquid_pairs_by_symbol(token_symbol: &str) -> String {
+        Key::LiquidPairsBySymbol(token_symbol.to_string()).to_key_string()
+    }
+
+    /// Block a symbol leader started leading at in `store_symbol_leads`.
+    /// Keyed by address alone, a token's symbol never changes.
+    pub fn symbol_lead_since(token_address: &str) -> String {
+        Key::SymbolLeadSince(token_address.to_string()).to_key_string()
+    }
+
+    /// Token a symbol resolves to in `store_canonical_symbols`.
+    pub fn canonical_token_by_symbol(token_symbol: &str) -> String {
+        Key::CanonicalTokenBySymbol(token_symbol.to_string()).to_key_string()
+    }
+
+    /// Latest Chainlink round of a feed, timestamp included, in `store_chainlink_answers`.
+    pub fn chainlink_answer(base: &str, quote: &str) -> String {
+        Key::ChainlinkAnswer(base.to_string(), quote.to_string()).to_key_string()
+    }
+
+    /// Closing USD price of a token for a UTC day in `store_historical_prices`.
+    /// `day` is formatted as `YYYY-MM-DD`, see `date::utc_day`.
+    pub fn daily_price_by_address(token_address: &str, day: &str) -> String {
+        Key::DailyPriceByTokenAddress(token_address.to_string(), day.to_string()).to_key_string()
+    }
+
+    /// Closing USD price of a token for a UTC hour in `store_historical_prices`.
+    /// `hour` is formatted as `YYYY-MM-DDTHH`, see `date::utc_hour`.
+    pub fn hourly_price_by_address(token_address: &str, hour: &str) -> String {
+        Key::HourlyPriceByTokenAddress(token_address.to_string(), hour.to_string()).to_key_string()
+    }
+
+    /// Latest depth of a pair in `store_pair_depth`.
+    pub fn pair_depth(pair_address: &str) -> String {
+        Key::PairDepth(pair_address.to_string()).to_key_string()
+    }
+
+    /// Depth of a token for a price move of `percent`, summed over its pairs, in
+    /// `store_token_depth`.
+    /// `direction` is `up` or `down`, the way the token's price moves.
+    pub fn token_depth(token_address: &str, percent: u32, direction: &str) -> String {
+        Key::TokenDepth(token_address.to_string(), percent, direction.to_string()).to_key_string()
+    }
+
+    /// Latest reserves of a pair in `store_pair_reserves`.
+    pub fn pair_reserves(pair_address: &str) -> String {
+        Key::PairReserves(pair_address.to_string()).to_key_string()
+    }
+
+    /// Latest reserves of the pair of two tokens in `store_pair_reserves`.
+    /// Tokens are sorted as in the pair, so either order gives the same key.
+    pub fn pair_reserves_by_tokens(token_a: &str, token_b: &str) -> String {
+        let (token0, token1) = if token_a < token_b {
+            (token_a, token_b)
+        } else {
+            (token_b, token_a)
+        };
+        Key::PairReservesByTokens(token0.to_string(), token1.to_string()).to_key_string()
+    }
+
+    /// First pair created for a token in `store_first_pairs`.
+    pub fn first_pair_by_token(token_address: &str) -> String {
+        Key::FirstPairByToken(token_address.to_string()).to_key_string()
+    }
+
+    /// Reserves of a pair at the start of its current window in `store_liquidity_windows`.
+    /// `grid` is 0, or 1 for the windows offset by half a window.
+    pub fn liquidity_window(pair_address: &str, grid: u32) -> String {
+        Key::LiquidityWindow(pair_address.to_string(), grid).to_key_string()
+    }
+
+    /// LP token supply of a pair in `store_lp_supply`.
+    pub fn lp_supply(pair_address: &str) -> String {
+        Key::LpSupply(pair_address.to_string()).to_key_string()
+    }
+
+    /// Latest liquidity drain of a flagged token in `store_flagged_tokens`.
+    pub fn flagged_token(token_address: &str) -> String {
+        Key::FlaggedToken(token_address.to_string()).to_key_string()
+    }
+
+    /// ETH/USD answer in the `chainlink_price_store` of the imported `chainlink_prices` package.
+    pub fn chainlink_eth_price() -> String {
+        Key::ChainlinkEthPrice.to_key_string()
+    }
+
+    /// `<base>/<quote>` answer in the `chainlink_price_store` of the imported `chainlink_prices`
+    /// package.
+    pub fn chainlink_price(base: &str, quote: &str) -> String {
+        Key::ChainlinkPrice(base.to_string(), quote.to_string()).to_key_string()
+    }
+}
+
+impl Key {
+    fn to_key_string(&self) -> String {
+        match self {
+            Key::Pair(address) => format!("Pair:{}", address),
+            Key::Token(address) => format!("Token:{}", address),
+            Key::EthUsdPrice => String::from("UsdPriceByTokenSymbol:ETH"),
+            Key::UsdPriceByTokenAddress(token_address) => {
+                format!("UsdPriceByTokenAddress:{}", token_address)
+            }
+            Key::UsdPriceByTokenSymbol(token_symbol) => {
+                format!("UsdPriceByTokenSymbol:{}", token_symbol)
+            }
+            Key::PriceByTokenAddress(token_address, quote_currency) => {
+                format!("PriceByTokenAddress:{}:{}", token_address, quote_currency)
+            }
+            Key::PriceByTokenSymbol(token_symbol, quote_currency) => {
+                format!("PriceByTokenSymbol:{}:{}", token_symbol, quote_currency)
+            }
+            Key::LastUpdatedByTokenAddress(token_address) => {
+                format!("LastUpdatedByTokenAddress:{}", token_address)
+            }
+            Key::PairLiquidity(pair_address) => format!("PairLiquidity:{}", pair_address),
+            Key::LiquidPairsBySymbol(token_symbol) => {
+                format!("LiquidPairsBySymbol:{}", token_symbol)
+            }
+            Key::SymbolLeadSince(token_address) => {
+                format!("SymbolLeadSince:{}", token_address)
+            }
+            Key::CanonicalTokenBySymbol(token_symbol) => {
+                format!("CanonicalTokenBySymbol:{}", token_symbol)
+            }
+            Key::ChainlinkAnswer(base, quote) => format!("ChainlinkAnswer:{}:{}", base, quote),
+            Key::DailyPriceByTokenAddress(token_address, day) => {
+                format!("DailyPriceByTokenAddress:{}:{}", token_address, day)
+            }
+            Key::HourlyPriceByTokenAddress(token_address, hour) => {
+                format!("HourlyPriceByTokenAddress:{}:{}", token_address, hour)
+            }
+            Key::PairDepth(pair_address) => format!("PairDepth:{}", pair_address),
+            Key::TokenDepth(token_address, percent, direction) => {
+                format!("TokenDepth:{}:{}:{}", token_address, percent, direction)
+            }
+            Key::PairReserves(pair_address) => format!("PairReserves:{}", pair_address),
+            Key::PairReservesByTokens(token0, token1) => {
+                format!("PairReservesByTokens:{}:{}", token0, token1)
+            }
+            Key::FirstPairByToken(token_address) => {
+                format!("FirstPairByToken:{}", token_address)
+            }
+            Key::LiquidityWindow(pair_address, grid) => {
+                format!("LiquidityWindow:{}:{}", pair_address, grid)
+            }
+            Key::LpSupply(pair_address) => format!("LpSupply:{}", pair_address),
+            Key::FlaggedToken(token_address) => format!("FlaggedToken:{}", token_address),
+            // Imported Chainlink Prices package keys
+            Key::ChainlinkEthPrice => String::from("price_by_symbol:ETH:USD"),
+            Key::ChainlinkPrice(base, quote) => format!("price_by_symbol:{}:{}", base, quote),
+        }
+    }
+}
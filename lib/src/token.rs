@@ -0,0 +1,72 @@
+use crate::pb::uniswap_pricing::v1::{
+    erc20_price::Source, Erc20Price, Erc20Token, LiquidityDrain, PairCreated, PairDepth,
+    QuotePrice, ReserveSnapshot, TokenLaunch, TokenLiquidity,
+};
+
+impl PairCreated {
+    pub fn token0_ref(&self) -> &Erc20Token {
+        self.token0.as_ref().unwrap()
+    }
+
+    pub fn token1_ref(&self) -> &Erc20Token {
+        self.token1.as_ref().unwrap()
+    }
+}
+
+impl PairDepth {
+    pub fn token0_ref(&self) -> &Erc20Token {
+        self.token0.as_ref().unwrap()
+    }
+
+    pub fn token1_ref(&self) -> &Erc20Token {
+        self.token1.as_ref().unwrap()
+    }
+}
+
+impl LiquidityDrain {
+    pub fn token0_ref(&self) -> &Erc20Token {
+        self.token0.as_ref().unwrap()
+    }
+
+    pub fn token1_ref(&self) -> &Erc20Token {
+        self.token1.as_ref().unwrap()
+    }
+}
+
+impl ReserveSnapshot {
+    pub fn token0_ref(&self) -> &Erc20Token {
+        self.token0.as_ref().unwrap()
+    }
+
+    pub fn token1_ref(&self) -> &Erc20Token {
+        self.token1.as_ref().unwrap()
+    }
+}
+
+impl Erc20Price {
+    pub fn token_ref(&self) -> &Erc20Token {
+        self.token.as_ref().unwrap()
+    }
+
+    pub fn source_name(&self) -> &'static str {
+        Source::from_i32(self.source).map_or("", |source| source.as_str_name())
+    }
+}
+
+impl QuotePrice {
+    pub fn token_ref(&self) -> &Erc20Token {
+        self.token.as_ref().unwrap()
+    }
+}
+
+impl TokenLiquidity {
+    pub fn token_ref(&self) -> &Erc20Token {
+        self.token.as_ref().unwrap()
+    }
+}
+
+impl TokenLaunch {
+    pub fn token_ref(&self) -> &Erc20Token {
+        self.token.as_ref().unwrap()
+    }
+}
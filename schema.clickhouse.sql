@@ -0,0 +1,17 @@
+-- Every price observation, keyed by `<token_address>-<block_number>-<ordinal>`
+create table if not exists price_ticks
+(
+    id            String,
+    block_number  UInt64,
+    block_time    DateTime,
+    ordinal       UInt64,
+    token_address String,
+    symbol        LowCardinality(String),
+    -- Written by `ch_out` truncated to 18 decimal places
+    price_usd     Decimal256(18),
+    source        LowCardinality(String),
+    pair_address  String
+)
+    engine = ReplacingMergeTree
+    partition by toYYYYMM(block_time)
+    order by (token_address, block_number, ordinal);
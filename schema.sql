@@ -0,0 +1,51 @@
+create table if not exists tokens
+(
+    address  text not null constraint tokens_pk primary key,
+    name     text,
+    symbol   text,
+    decimals integer
+);
+
+create table if not exists pairs
+(
+    address      text not null constraint pairs_pk primary key,
+    factory      text,
+    token0       text not null,
+    token1       text not null,
+    tx_hash      text,
+    block_number bigint,
+    block_time   timestamp
+);
+
+-- Latest price per token. `price_eth` keeps the token's last ETH price when a newer price has none.
+create table if not exists token_prices
+(
+    token_address text not null constraint token_prices_pk primary key,
+    symbol        text,
+    price_usd     numeric,
+    price_eth     numeric,
+    source        text,
+    pair_address  text,
+    block_number  bigint,
+    ordinal       bigint,
+    block_time    timestamp
+);
+
+-- Every price update, keyed by `<token_address>-<block_number>-<ordinal>`. `price_eth` is NULL
+-- when the price has no ETH price.
+create table if not exists token_price_history
+(
+    id            text not null constraint token_price_history_pk primary key,
+    token_address text not null,
+    symbol        text,
+    price_usd     numeric,
+    price_eth     numeric,
+    source        text,
+    pair_address  text,
+    block_number  bigint,
+    ordinal       bigint,
+    block_time    timestamp
+);
+
+create index if not exists token_price_history_token_block_idx
+    on token_price_history (token_address, block_number);
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use substreams::{scalar::BigDecimal, store::StoreGet, Hex};
+
+use crate::{
+    pb::uniswap_pricing::v1::PairCreated, pricing::Address, store_key_manager::StoreKey,
+    types::stable_coin::StablePrices,
+};
+
+// A pair created by the factory, with its token addresses decoded and the USD price of any
+// stablecoin side resolved.
+pub struct TrackedPair {
+    pub pair: PairCreated,
+    pub token0: Address,
+    pub token1: Address,
+    pub base_price0: Option<BigDecimal>,
+    pub base_price1: Option<BigDecimal>,
+}
+
+// Caches what pricing reads from the stores within a block, so a pair seen in several `Sync`
+// logs is read and decoded once. Must not outlive the block, the stores change between blocks.
+pub struct BlockContext<'a, P> {
+    pairs_store: &'a P,
+    stable_prices: HashMap<Address, Option<BigDecimal>>,
+    pairs: HashMap<Address, Option<TrackedPair>>,
+}
+
+impl<'a, P> BlockContext<'a, P>
+where
+    P: StoreGet<PairCreated>,
+{
+    pub fn new(stable_prices: &StablePrices, pairs_store: &'a P) -> Self {
+        BlockContext {
+            pairs_store,
+            stable_prices: stable_prices
+                .iter()
+                .filter_map(|(address, price)| Some((decode_address(address)?, price.clone())))
+                .collect(),
+            pairs: HashMap::new(),
+        }
+    }
+
+    // The pair at `address`, or `None` when it was not created by the tracked factory
+    pub fn pair(&mut self, address: &[u8]) -> Option<&TrackedPair> {
+        let address: Address = address.try_into().ok()?;
+        if !self.pairs.contains_key(&address) {
+            let pair = self.load_pair(&address);
+            self.pairs.insert(address, pair);
+        }
+        self.pairs.get(&address)?.as_ref()
+    }
+
+    // USD price of the stablecoin at `address`, `None` for any other token, see
+    // `StablePrices::usd_price`
+    pub fn stable_price(&self, address: &Address) -> Option<BigDecimal> {
+        self.stable_prices.get(address).cloned().flatten()
+    }
+
+    fn load_pair(&self, address: &Address) -> Option<TrackedPair> {
+        let pair = self
+            .pairs_store
+            .get_last(StoreKey::pair_key(&Hex::encode(address)))?;
+        let token0 = decode_address(&pair.token0_ref().address)?;
+        let token1 = decode_address(&pair.token1_ref().address)?;
+
+        Some(TrackedPair {
+            base_price0: self.stable_price(&token0),
+            base_price1: self.stable_price(&token1),
+            pair,
+            token0,
+            token1,
+        })
+    }
+}
+
+// Decodes a lowercase hex address without `0x` prefix, as stored in `Erc20Token.address`
+pub fn decode_address(address: &str) -> Option<Address> {
+    if address.len() != 40 {
+        return None;
+    }
+
+    let mut bytes = [0u8; 20];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(address.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+// Whether an address is the zero address, which pairs mint LP tokens from and burn them to
+pub fn is_zero_address(address: &[u8]) -> bool {
+    address.iter().all(|byte| *byte == 0)
+}
@@ -0,0 +1,19 @@
+use substreams::store::{StoreGet, StoreGetProto};
+
+use crate::{
+    constants::VERIFIED_TOKENS, pb::uniswap_pricing::v1::Erc20Token, store_key_manager::StoreKey,
+};
+
+// Whether `token` owns its symbol, and may therefore write symbol keyed prices. Verified symbols
+// always resolve to their listed address, others to the token recorded by `store_canonical_symbols`.
+pub fn is_canonical_symbol(
+    token: &Erc20Token,
+    canonical_store: &StoreGetProto<Erc20Token>,
+) -> bool {
+    match VERIFIED_TOKENS.get(token.symbol.as_str()) {
+        Some(address) => *address == token.address,
+        None => canonical_store
+            .get_last(StoreKey::canonical_token_by_symbol(&token.symbol))
+            .map_or(false, |canonical| canonical.address == token.address),
+    }
+}
@@ -0,0 +1,106 @@
+use std::str::FromStr;
+
+use substreams::{scalar::BigDecimal, store::StoreGet};
+
+use crate::{
+    constants::{CHAINLINK_FEED_ALIASES, VERIFIED_TOKENS},
+    pb::{
+        chainlink::v1::Price,
+        uniswap_pricing::v1::{erc20_price::Source, ChainlinkPrices, Erc20Price, Erc20Token},
+    },
+    store_key_manager::StoreKey,
+};
+
+// Default maximum age of the Chainlink ETH/USD answer, the feed's heartbeat on mainnet.
+pub const DEFAULT_ETH_USD_HEARTBEAT_SECONDS: i64 = 3600;
+
+// Base symbol of the Chainlink `<feed>/USD` feed pricing `token`. Only verified tokens are matched
+// to a feed, so a token merely reusing a symbol never picks up its Chainlink price.
+pub fn chainlink_feed(token: &Erc20Token) -> Option<&'static str> {
+    let (symbol, address) = VERIFIED_TOKENS.get_key_value(token.symbol.as_str())?;
+    if *address != token.address {
+        return None;
+    }
+    Some(
+        CHAINLINK_FEED_ALIASES
+            .get(symbol)
+            .copied()
+            .unwrap_or(*symbol),
+    )
+}
+
+// Address of the verified token priced by the Chainlink `<feed>/USD` feed.
+pub fn feed_token_address(feed: &str) -> Option<&'static str> {
+    let symbol = CHAINLINK_FEED_ALIASES
+        .iter()
+        .find(|(_, alias)| **alias == feed)
+        .map_or(feed, |(symbol, _)| *symbol);
+    VERIFIED_TOKENS.get(symbol).copied()
+}
+
+// Address of every verified token, with the base symbol of the Chainlink `<feed>/USD` feed that
+// would price it.
+pub fn verified_feeds() -> impl Iterator<Item = (&'static str, &'static str)> {
+    VERIFIED_TOKENS.iter().map(|(symbol, address)| {
+        (
+            *address,
+            CHAINLINK_FEED_ALIASES
+                .get(symbol)
+                .copied()
+                .unwrap_or(*symbol),
+        )
+    })
+}
+
+// Maximum age of a `<feed>/USD` answer: the ETH/USD feed, which also prices WETH, is held to its
+// own, shorter heartbeat.
+pub fn feed_heartbeat_seconds(
+    feed: &str,
+    heartbeat_seconds: i64,
+    eth_usd_heartbeat_seconds: i64,
+) -> i64 {
+    if feed == "ETH" {
+        eth_usd_heartbeat_seconds
+    } else {
+        heartbeat_seconds
+    }
+}
+
+// Latest `<base>/USD` round from `store_chainlink_answers`, or `None` when it is older than
+// `heartbeat_seconds` at `block_seconds`, as the feed has then stopped updating.
+pub fn fresh_usd_round<S: StoreGet<Price>>(
+    chainlink_answers_store: &S,
+    base: &str,
+    block_seconds: i64,
+    heartbeat_seconds: i64,
+) -> Option<Price> {
+    chainlink_answers_store
+        .get_last(StoreKey::chainlink_answer(base, "USD"))
+        .filter(|answer| block_seconds - answer.timestamp <= heartbeat_seconds)
+}
+
+// Answer of the latest fresh `<base>/USD` round, see `fresh_usd_round`.
+pub fn fresh_usd_answer<S: StoreGet<Price>>(
+    chainlink_answers_store: &S,
+    base: &str,
+    block_seconds: i64,
+    heartbeat_seconds: i64,
+) -> Option<BigDecimal> {
+    fresh_usd_round(
+        chainlink_answers_store,
+        base,
+        block_seconds,
+        heartbeat_seconds,
+    )
+    .and_then(|answer| BigDecimal::from_str(&answer.price).ok())
+}
+
+// Whether `price` gives way to Chainlink under `pricing_mode=chainlink_first`, as resolved by
+// `map_chainlink_prices`: a Uniswap price of a token with a fresh Chainlink USD answer.
+pub fn superseded_by_chainlink(chainlink_prices: &ChainlinkPrices, price: &Erc20Price) -> bool {
+    chainlink_prices.chainlink_first
+        && price.source != Source::Chainlink as i32
+        && chainlink_prices
+            .chainlink_priced_tokens
+            .contains(&price.token_ref().address)
+}
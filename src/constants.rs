@@ -1,12 +1,56 @@
+use std::collections::HashMap;
+
+use hex_literal::hex;
 use lazy_static;
 
+use crate::types::stable_coin::StableCoin;
+
 lazy_static::lazy_static! {
-    pub static ref STABLE_COINS: &'static [&'static str] = &[
-        "dac17f958d2ee523a2206206994597c13d831ec7", // USDT
-        "a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", // USDC
-        "4fabb145d64652a948d72533023f6e7a623c7c53", // BUSD
-        "6b175474e89094c44da98b954eedeac495271d0f" // DAI
+    // Stablecoins used to price other tokens, unless overridden by the `stablecoins` module param.
+    pub static ref DEFAULT_STABLE_COINS: Vec<StableCoin> = vec![
+        StableCoin::new("dac17f958d2ee523a2206206994597c13d831ec7", "USD", false, Some("USDT")),
+        StableCoin::new("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48", "USD", false, Some("USDC")),
+        StableCoin::new("4fabb145d64652a948d72533023f6e7a623c7c53", "USD", false, Some("BUSD")),
+        StableCoin::new("6b175474e89094c44da98b954eedeac495271d0f", "USD", false, Some("DAI")),
+        StableCoin::new("8e870d67f660d95d5be530380d0ec0bd388289e1", "USD", false, Some("USDP")),
+        StableCoin::new("0000000000085d4780b73119b644ae5ecd22b376", "USD", false, Some("TUSD")),
+        StableCoin::new("853d955acef822db058eb8505911ed77f175b99e", "USD", true, Some("FRAX")),
+        StableCoin::new("5f98805a4e8be255a32880fdec7f6728c6568ba0", "USD", false, Some("LUSD")),
+        StableCoin::new("056fd409e1d7a124bd7017459dfea2f387b6d5cd", "USD", false, Some("GUSD")),
+        StableCoin::new("6c3ea9036406852006290770bedfcaba0e23a0e8", "USD", false, Some("PYUSD")),
+        StableCoin::new("57ab1ec28d129707052df4df418d58a2d46d5f51", "USD", false, Some("sUSD")),
+        StableCoin::new("db25f211ab05b1c97d595516f45794528a807ad8", "EUR", false, None), // EURS
     ];
+
+    // Canonical token address for well known symbols. Prices for these symbols are only ever
+    // written under `UsdPriceByTokenSymbol` keys by the listed address, whatever the liquidity of
+    // other tokens sharing the symbol.
+    pub static ref VERIFIED_TOKENS: HashMap<&'static str, &'static str> = HashMap::from([
+        ("USDT", "dac17f958d2ee523a2206206994597c13d831ec7"),
+        ("USDC", "a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+        ("BUSD", "4fabb145d64652a948d72533023f6e7a623c7c53"),
+        ("DAI", "6b175474e89094c44da98b954eedeac495271d0f"),
+        ("WETH", "c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+        ("WBTC", "2260fac5e5542a773aa44fbcfedf7c193bc2c599"),
+        ("GRT", "c944e90c64b2c07662a292be6244bdf05cda44a7"),
+        ("LINK", "514910771af9ca656af840dff83e8264ecf986ca"),
+        ("UNI", "1f9840a85d5af5bf1d1762f925bdaddc4201f984"),
+        ("AAVE", "7fc66500c84a76ad7e9c93437bfc5ac33e2ddae9"),
+        ("MKR", "9f8f72aa9304c8b593d555f12ef6589cc3a579a2"),
+        ("COMP", "c00e94cb662c3520282e6f5717214004a7f26888"),
+        ("SNX", "c011a73ee8576fb46f5e1c5751ca3b9fe0af2a6f"),
+        ("CRV", "d533a949740bb3306d119cc777fa900ba034cd52"),
+        ("SUSHI", "6b3595068778dd592e39a122f4f5a5cf09c90fe2"),
+        ("YFI", "0bc529c00c6401aef6d220be8c6ea1667f6ad93e"),
+        ("MATIC", "7d1afa7b718fb893db30a3abc0cfc608aacfebb0"),
+        ("SHIB", "95ad61b0a150d79219dcf64e1e6cc01f0b64c4ce"),
+        ("LDO", "5a98fcbea516cf06857215779fd812ca3bef1b32"),
+    ]);
+
+    // Chainlink feeds are quoted for the underlying asset of wrapped tokens.
+    pub static ref CHAINLINK_FEED_ALIASES: HashMap<&'static str, &'static str> =
+        HashMap::from([("WETH", "ETH"), ("WBTC", "BTC")]);
 }
 
 pub const WETH_ADDRESS: &'static str = "c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2";
+pub const WETH_ADDRESS_BYTES: [u8; 20] = hex!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2");
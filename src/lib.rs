@@ -1,9 +1,14 @@
 mod abi;
+mod block_context;
+mod canonical_symbols;
+mod chainlink_feeds;
 mod modules;
 mod constants;
-mod pb;
+mod params;
+mod pricing;
 mod rpc;
-mod store_key_manager;
 mod types;
 
+use uniswap_pricing_lib::{pb, store_key_manager};
+
 substreams_ethereum::init!();
\ No newline at end of file
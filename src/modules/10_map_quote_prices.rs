@@ -0,0 +1,113 @@
+use std::str::FromStr;
+
+use substreams::{
+    pb::substreams::Clock,
+    scalar::BigDecimal,
+    store::{StoreGet, StoreGetProto},
+};
+
+use crate::{
+    chainlink_feeds::{fresh_usd_answer, superseded_by_chainlink},
+    params::Params,
+    pb::{
+        chainlink::v1::Price,
+        uniswap_pricing::v1::{ChainlinkPrices, Erc20Prices, QuotePrice, QuotePrices},
+    },
+};
+
+// Default maximum age of a `<QUOTE>/USD` answer, the heartbeat of the mainnet fiat feeds.
+const DEFAULT_QUOTE_HEARTBEAT_SECONDS: i64 = 86400;
+
+// Converts the USD prices of the block into the extra quote currencies listed in the params, using
+// the `<QUOTE>/USD` answer from `store_chainlink_answers`. Prices come from `map_uniswap_prices`
+// and `map_chainlink_prices`, skipping those `store_uniswap_prices` drops under
+// `pricing_mode=chainlink_first`.
+// Params: `quote_currencies=EUR,BTC,...`
+//         `quote_heartbeat_seconds=<seconds>`, after which a `<QUOTE>/USD` answer is stale
+#[substreams::handlers::map]
+fn map_quote_prices(
+    params: String,
+    clock: Clock,
+    prices: Erc20Prices,
+    chainlink_prices: ChainlinkPrices,
+    chainlink_answers_store: StoreGetProto<Price>,
+) -> Result<QuotePrices, substreams::errors::Error> {
+    let params = Params::parse(&params);
+    let quote_currencies: Vec<String> = params
+        .get_list("quote_currencies")
+        .into_iter()
+        .map(str::to_uppercase)
+        .collect();
+    let heartbeat_seconds =
+        params.get_or("quote_heartbeat_seconds", DEFAULT_QUOTE_HEARTBEAT_SECONDS)?;
+
+    let block_seconds = clock
+        .timestamp
+        .as_ref()
+        .map_or(0, |timestamp| timestamp.seconds);
+
+    Ok(QuotePrices {
+        items: quote_prices(
+            &quote_currencies,
+            heartbeat_seconds,
+            block_seconds,
+            &prices,
+            &chainlink_prices,
+            &chainlink_answers_store,
+        ),
+    })
+}
+
+pub(crate) fn quote_prices<S: StoreGet<Price>>(
+    quote_currencies: &[String],
+    heartbeat_seconds: i64,
+    block_seconds: i64,
+    prices: &Erc20Prices,
+    chainlink_prices: &ChainlinkPrices,
+    chainlink_answers_store: &S,
+) -> Vec<QuotePrice> {
+    // Resolve each quote currency's USD rate once for the block, skipping any without a fresh
+    // answer.
+    let quote_rates: Vec<(&String, BigDecimal)> = quote_currencies
+        .iter()
+        .filter_map(|quote_currency| {
+            let rate = fresh_usd_answer(
+                chainlink_answers_store,
+                quote_currency,
+                block_seconds,
+                heartbeat_seconds,
+            )?;
+            if rate == BigDecimal::zero() {
+                return None;
+            }
+            Some((quote_currency, rate))
+        })
+        .collect();
+
+    if quote_rates.is_empty() {
+        return vec![];
+    }
+
+    prices
+        .items
+        .iter()
+        .chain(chainlink_prices.items.iter())
+        .filter(|price| !superseded_by_chainlink(chainlink_prices, price))
+        .filter_map(|price| {
+            let price_usd = BigDecimal::from_str(price.price_usd.as_str()).ok()?;
+            Some(
+                quote_rates
+                    .iter()
+                    .map(move |(quote_currency, rate)| QuotePrice {
+                        token: price.token.clone(),
+                        quote_currency: quote_currency.to_string(),
+                        price: (price_usd.clone() / rate.clone()).to_string(),
+                        block_number: price.block_number,
+                        ordinal: price.ordinal,
+                        block_time: price.block_time.clone(),
+                    }),
+            )
+        })
+        .flatten()
+        .collect()
+}
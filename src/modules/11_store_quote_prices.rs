@@ -0,0 +1,30 @@
+use substreams::store::{StoreGetProto, StoreNew, StoreSet, StoreSetProto};
+
+use crate::{
+    canonical_symbols::is_canonical_symbol,
+    pb::uniswap_pricing::v1::{Erc20Token, QuotePrice, QuotePrices},
+    store_key_manager::StoreKey,
+};
+
+#[substreams::handlers::store]
+fn store_quote_prices(
+    prices: QuotePrices,
+    canonical_symbols_store: StoreGetProto<Erc20Token>,
+    output: StoreSetProto<QuotePrice>,
+) {
+    for price in prices.items {
+        output.set(
+            price.block_number,
+            StoreKey::price_by_address(&price.token_ref().address, &price.quote_currency),
+            &price,
+        );
+        // Only the canonical token for a symbol may write the symbol keyed price
+        if is_canonical_symbol(price.token_ref(), &canonical_symbols_store) {
+            output.set(
+                price.block_number,
+                StoreKey::price_by_symbol(&price.token_ref().symbol, &price.quote_currency),
+                &price,
+            );
+        }
+    }
+}
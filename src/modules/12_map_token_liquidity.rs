@@ -0,0 +1,137 @@
+use std::{collections::HashMap, str::FromStr};
+
+use substreams::{
+    scalar::BigDecimal,
+    store::{StoreGet, StoreGetProto},
+    Hex,
+};
+use substreams_ethereum::{pb::eth::v2 as eth, Event};
+
+use crate::{
+    abi,
+    constants::{VERIFIED_TOKENS, WETH_ADDRESS},
+    params::Params,
+    pb::uniswap_pricing::v1::{
+        Erc20Price, PairCreated, StableCoinPrices, TokenLiquidities, TokenLiquidity,
+    },
+    store_key_manager::StoreKey,
+    types::stable_coin::StablePrices,
+};
+
+// Minimum pair liquidity before a token can claim a symbol.
+const DEFAULT_MIN_LIQUIDITY_USD: u64 = 50000;
+// How far a token's liquidity must lead every other token of its symbol, and for how many blocks,
+// about a day on mainnet, before it takes the symbol over.
+const DEFAULT_TAKEOVER_MARGIN_PERCENT: u64 = 10;
+const DEFAULT_TAKEOVER_BLOCKS: u64 = 7200;
+
+// Emits the USD liquidity of every pair a token has against WETH or a stablecoin, as of the end of
+// the block so liquidity added and removed within a transaction is never seen. Used to pick the
+// canonical token for symbols that are not in the verified list.
+// Params: `min_liquidity_usd=<usd>&takeover_margin_percent=<percent>&takeover_blocks=<blocks>`
+#[substreams::handlers::map]
+fn map_token_liquidity(
+    params: String,
+    blk: eth::Block,
+    stable_coins: StableCoinPrices,
+    pairs_store: StoreGetProto<PairCreated>,
+    weth_price_store: StoreGetProto<Erc20Price>,
+) -> Result<TokenLiquidities, substreams::errors::Error> {
+    let params = Params::parse(&params);
+    let min_liquidity_usd = params.get_or(
+        "min_liquidity_usd",
+        BigDecimal::from(DEFAULT_MIN_LIQUIDITY_USD),
+    )?;
+    let takeover_margin_percent = params.get_or(
+        "takeover_margin_percent",
+        BigDecimal::from(DEFAULT_TAKEOVER_MARGIN_PERCENT),
+    )?;
+
+    Ok(TokenLiquidities {
+        items: token_liquidities(
+            &StablePrices::from_proto(&stable_coins),
+            &blk,
+            &pairs_store,
+            &weth_price_store,
+        ),
+        min_liquidity_usd: min_liquidity_usd.to_string(),
+        takeover_margin_percent: takeover_margin_percent.to_string(),
+        takeover_blocks: params.get_or("takeover_blocks", DEFAULT_TAKEOVER_BLOCKS)?,
+    })
+}
+
+pub(crate) fn token_liquidities<P, W>(
+    stable_prices: &StablePrices,
+    blk: &eth::Block,
+    pairs_store: &P,
+    weth_price_store: &W,
+) -> Vec<TokenLiquidity>
+where
+    P: StoreGet<PairCreated>,
+    W: StoreGet<Erc20Price>,
+{
+    let is_base_token = |address: &str| address == WETH_ADDRESS || stable_prices.contains(address);
+    // Index of each pair's entry, later `Sync` logs of the pair replace it
+    let mut indexes: HashMap<String, usize> = HashMap::new();
+    let mut liquidities: Vec<TokenLiquidity> = Vec::new();
+
+    for log in blk.receipts().flat_map(|view| view.receipt.logs.iter()) {
+        let event = match abi::pair::events::Sync::match_and_decode(log) {
+            Some(event) => event,
+            None => continue,
+        };
+        let pair = match pairs_store.get_last(StoreKey::pair_key(&Hex::encode(&log.address))) {
+            Some(pair) => pair,
+            None => continue,
+        };
+
+        let reserve0 = event.reserve0.to_decimal(pair.token0_ref().decimals);
+        let reserve1 = event.reserve1.to_decimal(pair.token1_ref().decimals);
+
+        let token0_address = pair.token0_ref().address.as_str();
+        let token1_address = pair.token1_ref().address.as_str();
+
+        // The reserve we know the USD value of, and the token it gives liquidity to
+        let (base_reserve, base_address, token) = if is_base_token(token0_address) {
+            (reserve0, token0_address, pair.token1_ref())
+        } else if is_base_token(token1_address) {
+            (reserve1, token1_address, pair.token0_ref())
+        } else {
+            continue;
+        };
+
+        // Verified symbols are never contested, so their liquidity is not tracked
+        if VERIFIED_TOKENS.contains_key(token.symbol.as_str()) {
+            continue;
+        }
+
+        let base_price = if base_address == WETH_ADDRESS {
+            weth_price_store
+                .get_last(StoreKey::eth_usd_price_key())
+                .and_then(|price| BigDecimal::from_str(&price.price_usd).ok())
+        } else {
+            stable_prices.usd_price(base_address)
+        };
+        let base_price = match base_price {
+            Some(base_price) => base_price,
+            None => continue,
+        };
+
+        let liquidity = TokenLiquidity {
+            token: Some(token.clone()),
+            pair_address: pair.pair_address.clone(),
+            liquidity_usd: (base_reserve * base_price * BigDecimal::from(2)).to_string(),
+            ordinal: log.ordinal,
+            block_number: blk.number,
+        };
+        match indexes.get(&pair.pair_address) {
+            Some(index) => liquidities[*index] = liquidity,
+            None => {
+                indexes.insert(pair.pair_address.clone(), liquidities.len());
+                liquidities.push(liquidity);
+            }
+        }
+    }
+
+    liquidities
+}
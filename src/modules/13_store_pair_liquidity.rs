@@ -0,0 +1,25 @@
+use substreams::store::{StoreNew, StoreSet, StoreSetProto};
+
+use crate::{
+    pb::uniswap_pricing::v1::{TokenLiquidities, TokenLiquidity},
+    store_key_manager::StoreKey,
+};
+
+// Latest liquidity of every pair against WETH or a stablecoin, under `PairLiquidity:<pair>`.
+#[substreams::handlers::store]
+fn store_pair_liquidity(liquidities: TokenLiquidities, output: StoreSetProto<TokenLiquidity>) {
+    set_pair_liquidities(liquidities, &output);
+}
+
+pub(crate) fn set_pair_liquidities<S: StoreSet<TokenLiquidity>>(
+    liquidities: TokenLiquidities,
+    output: &S,
+) {
+    for liquidity in liquidities.items {
+        output.set(
+            liquidity.ordinal,
+            StoreKey::pair_liquidity(&liquidity.pair_address),
+            &liquidity,
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use substreams::store::{StoreGet, StoreGetInt64, StoreNew, StoreSet, StoreSetProto};
+
+use crate::{
+    pb::uniswap_pricing::v1::{Erc20Token, SymbolLeaders},
+    store_key_manager::StoreKey,
+};
+
+// Records the canonical token for symbols that are not in the verified list. A token takes a
+// symbol over once it has led every other token of the symbol, the current holder included, by
+// the takeover margin for `takeover_blocks`, so flash loans and briefly outbidding or draining the
+// holder's pool do not move the symbol. Leads are checked when one of the symbol's pairs is next
+// updated.
+#[substreams::handlers::store]
+fn store_canonical_symbols(
+    leaders: SymbolLeaders,
+    leads_store: StoreGetInt64,
+    output: StoreSetProto<Erc20Token>,
+) {
+    set_canonical_symbols(leaders, &leads_store, &output);
+}
+
+pub(crate) fn set_canonical_symbols<L: StoreGet<i64>, S: StoreSet<Erc20Token>>(
+    leaders: SymbolLeaders,
+    leads_store: &L,
+    output: &S,
+) {
+    for leader in leaders.items {
+        let token = match &leader.leader {
+            Some(token) => token,
+            None => continue,
+        };
+        let since = match leads_store.get_last(StoreKey::symbol_lead_since(&token.address)) {
+            Some(since) => since as u64,
+            None => continue,
+        };
+
+        if leader.block_number.saturating_sub(since) >= leader.takeover_blocks {
+            output.set(
+                leader.ordinal,
+                StoreKey::canonical_token_by_symbol(&leader.symbol),
+                token,
+            );
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use substreams::store::{StoreNew, StoreSetIfNotExists, StoreSetIfNotExistsProto};
+
+use crate::{
+    pb::uniswap_pricing::v1::{Erc20Token, FactoryEvents},
+    store_key_manager::StoreKey,
+};
+
+#[substreams::handlers::store]
+fn store_tokens(events: FactoryEvents, output: StoreSetIfNotExistsProto<Erc20Token>) {
+    for event in events.pair_createds {
+        for token in [event.token0_ref(), event.token1_ref()] {
+            output.set_if_not_exists(event.ordinal, StoreKey::token_key(&token.address), token);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use std::str::FromStr;
+
+use substreams::{
+    scalar::BigDecimal,
+    store::{StoreGet, StoreGetProto},
+};
+use substreams_ethereum::pb::eth::v2 as eth;
+
+use crate::{
+    chainlink_feeds::{
+        feed_heartbeat_seconds, feed_token_address, fresh_usd_answer, verified_feeds,
+        DEFAULT_ETH_USD_HEARTBEAT_SECONDS,
+    },
+    params::Params,
+    pb::{
+        chainlink::v1::{Price, Prices},
+        uniswap_pricing::v1::{erc20_price::Source, ChainlinkPrices, Erc20Price, Erc20Token},
+    },
+    store_key_manager::StoreKey,
+    types::pricing_mode::PricingMode,
+};
+
+// Default maximum age of a token's Chainlink USD answer, the heartbeat of most mainnet USD feeds.
+const DEFAULT_CHAINLINK_HEARTBEAT_SECONDS: i64 = 86400;
+
+// Prices verified tokens straight from their Chainlink USD feed whenever a new answer comes in, and
+// lists the tokens whose feed is fresh enough to take precedence over Uniswap. Only active when
+// `pricing_mode=chainlink_first`, the resolved mode is carried in the output for
+// `store_uniswap_prices`.
+// Params: `pricing_mode=uniswap|chainlink_first`
+//         `chainlink_heartbeat_seconds=<seconds>`, after which a token's Chainlink answer is stale
+//         `eth_usd_heartbeat_seconds=<seconds>`, after which the ETH/USD answer is stale
+#[substreams::handlers::map]
+fn map_chainlink_prices(
+    params: String,
+    blk: eth::Block,
+    answers: Prices,
+    tokens_store: StoreGetProto<Erc20Token>,
+    chainlink_answers_store: StoreGetProto<Price>,
+) -> Result<ChainlinkPrices, substreams::errors::Error> {
+    let params = Params::parse(&params);
+    let pricing_mode = PricingMode::from_params(&params)?;
+    let heartbeat_seconds = params.get_or(
+        "chainlink_heartbeat_seconds",
+        DEFAULT_CHAINLINK_HEARTBEAT_SECONDS,
+    )?;
+    let eth_usd_heartbeat_seconds = params.get_or(
+        "eth_usd_heartbeat_seconds",
+        DEFAULT_ETH_USD_HEARTBEAT_SECONDS,
+    )?;
+
+    if pricing_mode != PricingMode::ChainlinkFirst {
+        return Ok(ChainlinkPrices::default());
+    }
+
+    Ok(ChainlinkPrices {
+        items: chainlink_prices(
+            heartbeat_seconds,
+            eth_usd_heartbeat_seconds,
+            &blk,
+            &answers,
+            &tokens_store,
+            &chainlink_answers_store,
+        ),
+        chainlink_first: true,
+        chainlink_priced_tokens: chainlink_priced_tokens(
+            heartbeat_seconds,
+            eth_usd_heartbeat_seconds,
+            blk.timestamp().seconds,
+            &chainlink_answers_store,
+        ),
+    })
+}
+
+// Addresses of the verified tokens whose Chainlink USD answer is fresh at `block_seconds`.
+pub(crate) fn chainlink_priced_tokens<A: StoreGet<Price>>(
+    heartbeat_seconds: i64,
+    eth_usd_heartbeat_seconds: i64,
+    block_seconds: i64,
+    chainlink_answers_store: &A,
+) -> Vec<String> {
+    verified_feeds()
+        .filter(|(_, feed)| {
+            fresh_usd_answer(
+                chainlink_answers_store,
+                feed,
+                block_seconds,
+                feed_heartbeat_seconds(feed, heartbeat_seconds, eth_usd_heartbeat_seconds),
+            )
+            .is_some()
+        })
+        .map(|(address, _)| address.to_string())
+        .collect()
+}
+
+pub(crate) fn chainlink_prices<T, A>(
+    heartbeat_seconds: i64,
+    eth_usd_heartbeat_seconds: i64,
+    blk: &eth::Block,
+    answers: &Prices,
+    tokens_store: &T,
+    chainlink_answers_store: &A,
+) -> Vec<Erc20Price>
+where
+    T: StoreGet<Erc20Token>,
+    A: StoreGet<Price>,
+{
+    // Held to the same heartbeat as the ETH price of `map_uniswap_prices`
+    let eth_price = fresh_usd_answer(
+        chainlink_answers_store,
+        "ETH",
+        blk.timestamp().seconds,
+        eth_usd_heartbeat_seconds,
+    )
+    .filter(|eth_price| eth_price != &BigDecimal::zero());
+    let eth_price_source = match eth_price {
+        Some(_) => Source::Chainlink,
+        None => Source::Unknown,
+    };
+    // Chainlink prices are ordered after every Uniswap price of the block
+    let ordinal = blk
+        .receipts()
+        .flat_map(|view| view.receipt.logs.iter())
+        .map(|log| log.ordinal)
+        .max()
+        .unwrap_or(0);
+
+    answers
+        .items
+        .iter()
+        .filter_map(|answer| {
+            let asset_pair = answer.asset_pair.as_ref()?;
+            if asset_pair.quote_token.as_ref()?.symbol != "USD" {
+                return None;
+            }
+            let feed = asset_pair.base_token.as_ref()?.symbol.as_str();
+            if blk.timestamp().seconds - answer.timestamp
+                > feed_heartbeat_seconds(feed, heartbeat_seconds, eth_usd_heartbeat_seconds)
+            {
+                return None;
+            }
+
+            let token_address = feed_token_address(feed)?;
+            let token = tokens_store.get_last(StoreKey::token_key(token_address))?;
+            let price_usd = BigDecimal::from_str(&answer.price).ok()?;
+
+            let price_eth = eth_price.as_ref().map_or_else(String::new, |eth_price| {
+                (price_usd.clone() / eth_price.clone()).to_string()
+            });
+
+            Some(Erc20Price {
+                token: Some(token),
+                price_usd: price_usd.to_string(),
+                price_eth,
+                block_number: blk.number,
+                ordinal,
+                source: Source::Chainlink as i32,
+                eth_price_source: eth_price_source as i32,
+                block_time: Some(blk.timestamp().to_owned()),
+                pair_address: String::new(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,28 @@
+use substreams::store::{StoreNew, StoreSet, StoreSetProto};
+
+use crate::{
+    pb::chainlink::v1::{Price, Prices},
+    store_key_manager::StoreKey,
+};
+
+// Keeps the latest Chainlink answer per feed, including its round timestamp which the imported
+// `chainlink_price_store` does not expose.
+#[substreams::handlers::store]
+fn store_chainlink_answers(answers: Prices, output: StoreSetProto<Price>) {
+    for answer in answers.items {
+        let asset_pair = match &answer.asset_pair {
+            Some(asset_pair) => asset_pair,
+            None => continue,
+        };
+
+        if let (Some(base_token), Some(quote_token)) =
+            (&asset_pair.base_token, &asset_pair.quote_token)
+        {
+            output.set(
+                0,
+                StoreKey::chainlink_answer(&base_token.symbol, &quote_token.symbol),
+                &answer,
+            );
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::str::FromStr;
+
+use substreams::{
+    pb::substreams::Clock,
+    scalar::BigDecimal,
+    store::{StoreGet, StoreGetProto},
+};
+
+use crate::{
+    chainlink_feeds::{
+        chainlink_feed, feed_heartbeat_seconds, fresh_usd_round, DEFAULT_ETH_USD_HEARTBEAT_SECONDS,
+    },
+    params::Params,
+    pb::{
+        chainlink::v1::Price,
+        uniswap_pricing::v1::{erc20_price::Source, Erc20Prices, PriceDeviation, PriceDeviations},
+    },
+};
+
+// Default maximum age of a token's Chainlink USD answer, the heartbeat of most mainnet USD feeds.
+const DEFAULT_CHAINLINK_HEARTBEAT_SECONDS: i64 = 86400;
+
+// Compares every price derived from a Uniswap pool against the token's Chainlink USD feed, for
+// tokens that have a fresh answer. Large deviations point at oracle drift, a depeg or pool
+// manipulation. Reference prices are skipped, a stablecoin's may be its Chainlink answer itself.
+// Params: `min_deviation_percent=<percent>`, deviations smaller in absolute value are left out
+//         `chainlink_heartbeat_seconds=<seconds>`, after which a token's Chainlink answer is stale
+//         `eth_usd_heartbeat_seconds=<seconds>`, after which the ETH/USD answer is stale
+#[substreams::handlers::map]
+fn map_price_deviations(
+    params: String,
+    clock: Clock,
+    prices: Erc20Prices,
+    chainlink_answers_store: StoreGetProto<Price>,
+) -> Result<PriceDeviations, substreams::errors::Error> {
+    let params = Params::parse(&params);
+    let min_deviation_percent = params.get_or("min_deviation_percent", BigDecimal::zero())?;
+    let heartbeat_seconds = params.get_or(
+        "chainlink_heartbeat_seconds",
+        DEFAULT_CHAINLINK_HEARTBEAT_SECONDS,
+    )?;
+    let eth_usd_heartbeat_seconds = params.get_or(
+        "eth_usd_heartbeat_seconds",
+        DEFAULT_ETH_USD_HEARTBEAT_SECONDS,
+    )?;
+
+    let block_seconds = clock
+        .timestamp
+        .as_ref()
+        .map_or(0, |timestamp| timestamp.seconds);
+
+    Ok(PriceDeviations {
+        items: price_deviations(
+            &min_deviation_percent,
+            heartbeat_seconds,
+            eth_usd_heartbeat_seconds,
+            block_seconds,
+            &prices,
+            &chainlink_answers_store,
+        ),
+    })
+}
+
+pub(crate) fn price_deviations<S: StoreGet<Price>>(
+    min_deviation_percent: &BigDecimal,
+    heartbeat_seconds: i64,
+    eth_usd_heartbeat_seconds: i64,
+    block_seconds: i64,
+    prices: &Erc20Prices,
+    chainlink_answers_store: &S,
+) -> Vec<PriceDeviation> {
+    prices
+        .items
+        .iter()
+        .filter(|price| price.source == Source::Uniswap as i32)
+        .filter_map(|price| {
+            let feed = chainlink_feed(price.token_ref())?;
+            // A feed that stopped updating says nothing about the current pool price
+            let answer = fresh_usd_round(
+                chainlink_answers_store,
+                feed,
+                block_seconds,
+                feed_heartbeat_seconds(feed, heartbeat_seconds, eth_usd_heartbeat_seconds),
+            )?;
+
+            let uniswap_price = BigDecimal::from_str(&price.price_usd).ok()?;
+            let chainlink_price = BigDecimal::from_str(&answer.price).ok()?;
+            if chainlink_price == BigDecimal::zero() {
+                return None;
+            }
+
+            let deviation =
+                (uniswap_price - chainlink_price.clone()) / chainlink_price * BigDecimal::from(100);
+            let magnitude = if deviation < BigDecimal::zero() {
+                BigDecimal::zero() - deviation.clone()
+            } else {
+                deviation.clone()
+            };
+            if &magnitude < min_deviation_percent {
+                return None;
+            }
+
+            Some(PriceDeviation {
+                token: price.token.clone(),
+                pair_address: price.pair_address.clone(),
+                uniswap_price_usd: price.price_usd.clone(),
+                chainlink_price_usd: answer.price,
+                deviation_percent: deviation.to_string(),
+                chainlink_timestamp: answer.timestamp,
+                block_number: price.block_number,
+                ordinal: price.ordinal,
+            })
+        })
+        .collect()
+}
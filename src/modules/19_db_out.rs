@@ -0,0 +1,98 @@
+use substreams::{
+    pb::substreams::store_delta::Operation,
+    store::{DeltaProto, Deltas},
+};
+use substreams_database_change::{
+    pb::database::DatabaseChanges,
+    tables::{Row, Tables},
+};
+
+use crate::{
+    pb::uniswap_pricing::v1::{Erc20Price, Erc20Token, FactoryEvents},
+    store_key_manager::StoreKey,
+};
+
+// Postgres output for `substreams-sink-sql`, see `schema.sql` for the tables.
+#[substreams::handlers::map]
+fn db_out(
+    events: FactoryEvents,
+    tokens_deltas: Deltas<DeltaProto<Erc20Token>>,
+    prices_deltas: Deltas<DeltaProto<Erc20Price>>,
+) -> Result<DatabaseChanges, substreams::errors::Error> {
+    let mut tables = Tables::new();
+
+    for delta in tokens_deltas.deltas {
+        if delta.operation == Operation::Create {
+            let token = delta.new_value;
+            tables
+                .create_row("tokens", &token.address)
+                .set("name", token.name)
+                .set("symbol", token.symbol)
+                .set("decimals", token.decimals);
+        }
+    }
+
+    for event in events.pair_createds {
+        tables
+            .create_row("pairs", &event.pair_address)
+            .set("factory", &event.factory)
+            .set("token0", &event.token0_ref().address)
+            .set("token1", &event.token1_ref().address)
+            .set("tx_hash", &event.tx_hash)
+            .set("block_number", event.block_number)
+            .set("block_time", event.block_time.as_ref().unwrap());
+    }
+
+    set_prices(&mut tables, prices_deltas.deltas);
+
+    Ok(tables.to_database_changes())
+}
+
+pub(crate) fn set_prices(tables: &mut Tables, deltas: Vec<DeltaProto<Erc20Price>>) {
+    for delta in deltas {
+        let price = delta.new_value;
+
+        // Each price is stored under both its address and symbol keys, only the address one is used
+        if delta.key != StoreKey::usd_price_by_address(&price.token_ref().address) {
+            continue;
+        }
+
+        let latest = match delta.operation {
+            Operation::Create => tables.create_row("token_prices", &price.token_ref().address),
+            Operation::Update => tables.update_row("token_prices", &price.token_ref().address),
+            _ => continue,
+        };
+        set_price_columns(latest, &price);
+
+        let history_id = format!(
+            "{}-{}-{}",
+            price.token_ref().address,
+            price.block_number,
+            price.ordinal
+        );
+        set_price_columns(
+            tables
+                .create_row("token_price_history", history_id)
+                .set("token_address", &price.token_ref().address),
+            &price,
+        );
+    }
+}
+
+fn set_price_columns(row: &mut Row, price: &Erc20Price) {
+    row.set("symbol", &price.token_ref().symbol)
+        .set("price_usd", &price.price_usd)
+        .set("source", price.source_name())
+        .set("pair_address", &price.pair_address)
+        .set("block_number", price.block_number)
+        .set("ordinal", price.ordinal);
+    // Postgres rejects an empty string in a numeric column, so a price without an ETH price leaves
+    // the column out: NULL in a new row, the previous ETH price of the token in `token_prices`.
+    if !price.price_eth.is_empty() {
+        row.set("price_eth", &price.price_eth);
+    }
+
+    if let Some(block_time) = &price.block_time {
+        row.set("block_time", block_time);
+    }
+}
@@ -4,17 +4,25 @@ use substreams_ethereum::{pb::eth::v2 as eth, Event};
 
 use crate::{
     abi,
-    pb::uniswap_pricing::v1::{FactoryEvents, PairCreated},
+    pb::uniswap_pricing::v1::{Erc20Token, FactoryEvents, PairCreated},
     rpc::erc20::get_erc20_token,
 };
 
 // UniswapV2 Registry contract
 // TODO: May need to consider passing this in as a param in the manifest to support different networks.
-const TRACKED_CONTRACT: [u8; 20] = hex!("5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f");
+pub(crate) const TRACKED_CONTRACT: [u8; 20] = hex!("5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f");
 
 #[substreams::handlers::map]
 fn map_pair_created_events(blk: eth::Block) -> Result<FactoryEvents, substreams::errors::Error> {
-    Ok(FactoryEvents {
+    Ok(pair_created_events(&blk, get_erc20_token))
+}
+
+// Token metadata is resolved through `get_token`, an RPC call when running as a module.
+pub(crate) fn pair_created_events<F>(blk: &eth::Block, get_token: F) -> FactoryEvents
+where
+    F: Fn(Vec<u8>) -> Option<Erc20Token>,
+{
+    FactoryEvents {
         pair_createds: blk
             .receipts()
             .flat_map(|view| {
@@ -26,8 +34,8 @@ fn map_pair_created_events(blk: eth::Block) -> Result<FactoryEvents, substreams:
                         if let Some(event) =
                             abi::factory::events::PairCreated::match_and_decode(log)
                         {
-                            let token0 = get_erc20_token(event.token0)?;
-                            let token1 = get_erc20_token(event.token1)?;
+                            let token0 = get_token(event.token0)?;
+                            let token1 = get_token(event.token1)?;
 
                             return Some(PairCreated {
                                 tx_hash: Hex(&view.transaction.hash).to_string(),
@@ -39,11 +47,12 @@ fn map_pair_created_events(blk: eth::Block) -> Result<FactoryEvents, substreams:
                                 token1: Some(token1),
                                 pair_address: Hex::encode(event.pair),
                                 factory: Hex::encode(&log.address),
+                                tx_from: Hex::encode(&view.transaction.from),
                             });
                         }
                         None
                     })
             })
             .collect(),
-    })
+    }
 }
@@ -0,0 +1,125 @@
+use std::str::FromStr;
+
+use substreams::{
+    pb::substreams::store_delta::Operation,
+    scalar::{BigDecimal, BigInt},
+    store::{DeltaProto, Deltas},
+};
+use substreams_entity_change::{
+    pb::entity::{EntityChanges, Value},
+    tables::Tables,
+};
+
+use crate::{
+    pb::uniswap_pricing::v1::{Erc20Price, Erc20Token, FactoryEvents},
+    store_key_manager::StoreKey,
+};
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+// Entity changes for a Substreams powered subgraph, see `schema.graphql` for the entities.
+#[substreams::handlers::map]
+fn graph_out(
+    events: FactoryEvents,
+    tokens_deltas: Deltas<DeltaProto<Erc20Token>>,
+    prices_deltas: Deltas<DeltaProto<Erc20Price>>,
+    weth_prices_deltas: Deltas<DeltaProto<Erc20Price>>,
+) -> Result<EntityChanges, substreams::errors::Error> {
+    let mut tables = Tables::new();
+
+    for delta in tokens_deltas.deltas {
+        if delta.operation == Operation::Create {
+            let token = delta.new_value;
+            tables
+                .create_row("Token", &token.address)
+                .set("name", token.name)
+                .set("symbol", token.symbol)
+                .set("decimals", token.decimals as i32);
+        }
+    }
+
+    for event in events.pair_createds {
+        tables
+            .create_row("Pair", &event.pair_address)
+            .set("token0", &event.token0_ref().address)
+            .set("token1", &event.token1_ref().address)
+            .set("factory", &event.factory)
+            .set("txHash", &event.tx_hash)
+            .set("createdAtBlockNumber", BigInt::from(event.block_number))
+            .set(
+                "createdAtTimestamp",
+                BigInt::from(event.block_time.as_ref().unwrap().seconds),
+            );
+    }
+
+    set_prices(&mut tables, prices_deltas.deltas);
+
+    for delta in weth_prices_deltas.deltas {
+        let price = delta.new_value;
+        if let Ok(eth_price) = BigDecimal::from_str(&price.price_usd) {
+            let bundle = match delta.operation {
+                Operation::Create => tables.create_row("Bundle", "1"),
+                Operation::Update => tables.update_row("Bundle", "1"),
+                _ => continue,
+            };
+            bundle
+                .set("ethPriceUSD", eth_price)
+                .set("blockNumber", BigInt::from(price.block_number));
+        }
+    }
+
+    Ok(tables.to_entity_changes())
+}
+
+// `TokenPrice` and `TokenDayData` entities of the price updates.
+pub(crate) fn set_prices(tables: &mut Tables, deltas: Vec<DeltaProto<Erc20Price>>) {
+    for delta in deltas {
+        let price = delta.new_value;
+
+        // Each price is stored under both its address and symbol keys, only the address one is used
+        if delta.key != StoreKey::usd_price_by_address(&price.token_ref().address) {
+            continue;
+        }
+        let price_usd = match BigDecimal::from_str(&price.price_usd) {
+            Ok(price_usd) => price_usd,
+            Err(_) => continue,
+        };
+        let timestamp = price.block_time.as_ref().map_or(0, |ts| ts.seconds);
+
+        let token_price = match delta.operation {
+            Operation::Create => tables.create_row("TokenPrice", &price.token_ref().address),
+            Operation::Update => tables.update_row("TokenPrice", &price.token_ref().address),
+            _ => continue,
+        };
+        token_price
+            .set("token", &price.token_ref().address)
+            .set("priceUSD", price_usd.clone())
+            .set("source", price.source_name())
+            .set("pair", &price.pair_address)
+            .set("blockNumber", BigInt::from(price.block_number))
+            .set("timestamp", BigInt::from(timestamp));
+        // Set to null rather than left at the previous price when no ETH price is known
+        match BigDecimal::from_str(&price.price_eth) {
+            Ok(price_eth) => {
+                token_price.set("priceETH", price_eth);
+            }
+            Err(_) => {
+                token_price
+                    .columns
+                    .insert(String::from("priceETH"), Value { typed: None });
+            }
+        }
+
+        // The last update of the day is its closing price
+        let day_id = timestamp / SECONDS_PER_DAY;
+        tables
+            .update_row(
+                "TokenDayData",
+                format!("{}-{}", price.token_ref().address, day_id),
+            )
+            .set("token", &price.token_ref().address)
+            .set("date", (day_id * SECONDS_PER_DAY) as i32)
+            .set("priceUSD", price_usd)
+            .set("blockNumber", BigInt::from(price.block_number));
+    }
+}
@@ -0,0 +1,73 @@
+use std::{iter, str::FromStr};
+
+use substreams::scalar::BigDecimal;
+use substreams_database_change::{pb::database::DatabaseChanges, tables::Tables};
+
+use crate::pb::uniswap_pricing::v1::{ChainlinkPrices, Erc20Prices};
+
+// Scale of the `Decimal256` price columns
+const PRICE_SCALE: usize = 18;
+
+// ClickHouse output for `substreams-sink-sql`, see `schema.clickhouse.sql` for the table.
+// Every price observation with a USD price and a block time is written, including those
+// `store_uniswap_prices` does not keep.
+#[substreams::handlers::map]
+fn ch_out(
+    uniswap_prices: Erc20Prices,
+    chainlink_prices: ChainlinkPrices,
+) -> Result<DatabaseChanges, substreams::errors::Error> {
+    let mut tables = Tables::new();
+
+    for price in uniswap_prices
+        .items
+        .iter()
+        .chain(chainlink_prices.items.iter())
+    {
+        let price_usd = match to_price_decimal(&price.price_usd) {
+            Some(price_usd) => price_usd,
+            None => continue,
+        };
+        // The table is partitioned by block time, a price without one has no place in it
+        let block_time = match &price.block_time {
+            Some(block_time) => block_time,
+            None => continue,
+        };
+        let id = format!(
+            "{}-{}-{}",
+            price.token_ref().address,
+            price.block_number,
+            price.ordinal
+        );
+        tables
+            .create_row("price_ticks", id)
+            .set("block_number", price.block_number)
+            .set("block_time", block_time)
+            .set("ordinal", price.ordinal)
+            .set("token_address", &price.token_ref().address)
+            .set("symbol", &price.token_ref().symbol)
+            .set("price_usd", &price_usd)
+            .set("source", price.source_name())
+            .set("pair_address", &price.pair_address);
+    }
+
+    Ok(tables.to_database_changes())
+}
+
+// `price` with exactly `PRICE_SCALE` fractional digits, truncated, which is the value ClickHouse
+// stores. Writing it out keeps the sink from depending on how the server parses longer decimals.
+pub(crate) fn to_price_decimal(price: &str) -> Option<String> {
+    // Prices are written by `BigDecimal` in plain notation
+    BigDecimal::from_str(price).ok()?;
+    let (integer, fraction) = price.split_once('.').unwrap_or((price, ""));
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if !digits(integer.trim_start_matches('-')) || !digits(fraction) {
+        return None;
+    }
+
+    let fraction: String = fraction
+        .chars()
+        .chain(iter::repeat('0'))
+        .take(PRICE_SCALE)
+        .collect();
+    Some(format!("{}.{}", integer, fraction))
+}
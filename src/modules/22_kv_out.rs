@@ -0,0 +1,30 @@
+use prost::Message;
+use substreams::{
+    pb::substreams::store_delta::Operation,
+    store::{DeltaProto, Deltas},
+};
+use substreams_sink_kv::pb::sf::substreams::sink::kv::v1::KvOperations;
+
+use crate::pb::uniswap_pricing::v1::Erc20Price;
+
+// Key-value output for `substreams-sink-kv`. Keys are the `store_uniswap_prices` keys, so
+// `UsdPriceByTokenAddress:<address>` and `UsdPriceByTokenSymbol:<symbol>`, and values are the
+// encoded `Erc20Price`.
+#[substreams::handlers::map]
+fn kv_out(
+    prices_deltas: Deltas<DeltaProto<Erc20Price>>,
+) -> Result<KvOperations, substreams::errors::Error> {
+    let mut kv_ops = KvOperations::default();
+
+    for delta in prices_deltas.deltas {
+        match delta.operation {
+            Operation::Create | Operation::Update => {
+                kv_ops.push_new(delta.key, delta.new_value.encode_to_vec(), delta.ordinal)
+            }
+            Operation::Delete => kv_ops.push_delete(delta.key, delta.ordinal),
+            _ => {}
+        }
+    }
+
+    Ok(kv_ops)
+}
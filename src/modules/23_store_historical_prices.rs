@@ -0,0 +1,48 @@
+use substreams::{
+    pb::substreams::store_delta::Operation,
+    store::{DeltaProto, Deltas, StoreNew, StoreSet, StoreSetProto},
+};
+use uniswap_pricing_lib::date::{utc_day, utc_hour};
+
+use crate::{pb::uniswap_pricing::v1::Erc20Price, store_key_manager::StoreKey};
+
+// Keeps the closing price of every token per UTC day and hour. Each key is overwritten until its
+// day or hour is over, after which it holds the last price of that period.
+#[substreams::handlers::store]
+fn store_historical_prices(
+    prices_deltas: Deltas<DeltaProto<Erc20Price>>,
+    output: StoreSetProto<Erc20Price>,
+) {
+    set_historical_prices(prices_deltas.deltas, &output);
+}
+
+pub(crate) fn set_historical_prices<S: StoreSet<Erc20Price>>(
+    deltas: Vec<DeltaProto<Erc20Price>>,
+    output: &S,
+) {
+    for delta in deltas {
+        if delta.operation != Operation::Create && delta.operation != Operation::Update {
+            continue;
+        }
+
+        let price = delta.new_value;
+        let address = &price.token_ref().address;
+
+        // Each price is stored under both its address and symbol keys, only the address one is used
+        if delta.key != StoreKey::usd_price_by_address(address) {
+            continue;
+        }
+        if let Some(block_time) = &price.block_time {
+            output.set(
+                delta.ordinal,
+                StoreKey::daily_price_by_address(address, &utc_day(block_time.seconds)),
+                &price,
+            );
+            output.set(
+                delta.ordinal,
+                StoreKey::hourly_price_by_address(address, &utc_hour(block_time.seconds)),
+                &price,
+            );
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::str::FromStr;
+
+use substreams::{
+    pb::substreams::store_delta::Operation,
+    scalar::BigDecimal,
+    store::{DeltaProto, Deltas},
+};
+
+use crate::{
+    params::Params,
+    pb::uniswap_pricing::v1::{Erc20Price, PriceChange, PriceChanges},
+    store_key_manager::StoreKey,
+};
+
+// Emits the price changes written to `store_uniswap_prices`, with the old and new price.
+// Params: `min_change_percent=<percent>`, changes smaller in absolute value are left out
+#[substreams::handlers::map]
+fn map_price_changes(
+    params: String,
+    prices_deltas: Deltas<DeltaProto<Erc20Price>>,
+) -> Result<PriceChanges, substreams::errors::Error> {
+    let min_change_percent =
+        Params::parse(&params).get_or("min_change_percent", BigDecimal::zero())?;
+
+    Ok(PriceChanges {
+        items: price_changes(&min_change_percent, prices_deltas.deltas),
+    })
+}
+
+pub(crate) fn price_changes(
+    min_change_percent: &BigDecimal,
+    deltas: Vec<DeltaProto<Erc20Price>>,
+) -> Vec<PriceChange> {
+    deltas
+        .into_iter()
+        .filter(|delta| delta.operation == Operation::Update)
+        // Each price is stored under both its address and symbol keys, only the address one is used
+        .filter(|delta| {
+            delta.key == StoreKey::usd_price_by_address(&delta.new_value.token_ref().address)
+        })
+        .filter_map(|delta| {
+            let old_price = BigDecimal::from_str(&delta.old_value.price_usd).ok()?;
+            let new_price = BigDecimal::from_str(&delta.new_value.price_usd).ok()?;
+            if old_price == BigDecimal::zero() {
+                return None;
+            }
+
+            let change = (new_price - old_price.clone()) / old_price * BigDecimal::from(100);
+            let magnitude = if change < BigDecimal::zero() {
+                BigDecimal::zero() - change.clone()
+            } else {
+                change.clone()
+            };
+            if &magnitude < min_change_percent {
+                return None;
+            }
+
+            Some(PriceChange {
+                old_price: Some(delta.old_value),
+                new_price: Some(delta.new_value),
+                change_percent: change.to_string(),
+            })
+        })
+        .collect()
+}
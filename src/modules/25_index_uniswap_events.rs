@@ -0,0 +1,107 @@
+use hex_literal::hex;
+use substreams::pb::sf::substreams::index::v1::Keys;
+use substreams_ethereum::pb::eth::v2 as eth;
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::{abi, modules::map_pair_created_events::TRACKED_CONTRACT};
+
+// Keys used in the `blockFilter` queries of `substreams.yaml`
+pub(crate) const PAIR_CREATED_KEY: &str = "uniswap_v2:pair_created";
+pub(crate) const SYNC_KEY: &str = "uniswap_v2:sync";
+
+// Hash of the Uniswap V2 pair creation code, the factory deploys every pair with CREATE2 from it
+const PAIR_INIT_CODE_HASH: [u8; 32] =
+    hex!("96e8ac4277198ff8b6f785478aa9a39f403cb768dd02cbee326c3e7da348845f");
+
+// Block index of the logs the pricing modules look at, so blocks without any can be skipped.
+// Index modules cannot read stores, so a `Sync` log is matched to the factory through the pair
+// address instead, see `is_factory_pair`.
+#[substreams::handlers::map]
+fn index_uniswap_events(blk: eth::Block) -> Result<Keys, substreams::errors::Error> {
+    Ok(uniswap_event_keys(&blk))
+}
+
+pub(crate) fn uniswap_event_keys(blk: &eth::Block) -> Keys {
+    let mut pair_created = false;
+    let mut sync = false;
+
+    for view in blk.receipts() {
+        let logs = &view.receipt.logs;
+        for log in logs {
+            if log.address == TRACKED_CONTRACT && abi::factory::events::PairCreated::match_log(log)
+            {
+                pair_created = true;
+            } else if !sync
+                && abi::pair::events::Sync::match_log(log)
+                && is_factory_pair(&log.address, logs)
+            {
+                sync = true;
+            }
+        }
+    }
+
+    let mut keys = Keys::default();
+    if pair_created {
+        keys.keys.push(PAIR_CREATED_KEY.to_string());
+    }
+    if sync {
+        keys.keys.push(SYNC_KEY.to_string());
+    }
+    keys
+}
+
+// Whether `pair` is the factory's pair of two of the tokens moved in or out of it within the
+// transaction. Forks emit the same `Sync` logs, but their pairs are deployed at other addresses.
+// A `Sync` without two such tokens, e.g. `sync()` called after a rebase, cannot be told apart and
+// is kept.
+fn is_factory_pair(pair: &[u8], logs: &[eth::Log]) -> bool {
+    let mut tokens: Vec<&[u8]> = Vec::new();
+    for log in logs.iter().filter(|log| log.address != pair) {
+        let moves_pair_funds = abi::erc20::events::Transfer::match_and_decode(log)
+            .map_or(false, |transfer| {
+                transfer.from == pair || transfer.to == pair
+            });
+        if moves_pair_funds && !tokens.contains(&log.address.as_slice()) {
+            tokens.push(&log.address);
+        }
+    }
+    if tokens.len() < 2 {
+        return true;
+    }
+
+    tokens.iter().enumerate().any(|(i, token_a)| {
+        tokens[i + 1..]
+            .iter()
+            .any(|token_b| pair_address(token_a, token_b) == pair)
+    })
+}
+
+// CREATE2 address of the factory's pair of two tokens, as `UniswapV2Library.pairFor` computes it
+fn pair_address(token_a: &[u8], token_b: &[u8]) -> [u8; 20] {
+    let (token0, token1) = if token_a < token_b {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    };
+    let salt = keccak256(&[token0, token1]);
+    let hash = keccak256(&[
+        &[0xffu8][..],
+        &TRACKED_CONTRACT[..],
+        &salt[..],
+        &PAIR_INIT_CODE_HASH[..],
+    ]);
+
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+fn keccak256(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
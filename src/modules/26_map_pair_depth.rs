@@ -0,0 +1,115 @@
+use std::str::FromStr;
+
+use substreams::{
+    scalar::BigDecimal,
+    store::{StoreGet, StoreGetProto},
+};
+use substreams_ethereum::pb::eth::v2 as eth;
+
+use crate::{
+    abi,
+    block_context::BlockContext,
+    constants::WETH_ADDRESS_BYTES,
+    pb::uniswap_pricing::v1::{
+        DepthLevel, Erc20Price, PairCreated, PairDepth, PairDepths, StableCoinPrices,
+    },
+    pricing::{depth, DEPTH_PERCENTS},
+    store_key_manager::StoreKey,
+    types::stable_coin::StablePrices,
+};
+
+// Emits the depth of every pair holding WETH or a stablecoin on each `Sync`: the USD amount that
+// can be traded before the price moves by 1%, 2% and 5% either way, see `pricing::depth`.
+#[substreams::handlers::map]
+fn map_pair_depth(
+    blk: eth::Block,
+    stable_coins: StableCoinPrices,
+    pairs_store: StoreGetProto<PairCreated>,
+    weth_price_store: StoreGetProto<Erc20Price>,
+) -> Result<PairDepths, substreams::errors::Error> {
+    Ok(PairDepths {
+        items: pair_depths(
+            &StablePrices::from_proto(&stable_coins),
+            &blk,
+            &pairs_store,
+            &weth_price_store,
+        ),
+    })
+}
+
+pub(crate) fn pair_depths<P, W>(
+    stable_prices: &StablePrices,
+    blk: &eth::Block,
+    pairs_store: &P,
+    weth_price_store: &W,
+) -> Vec<PairDepth>
+where
+    P: StoreGet<PairCreated>,
+    W: StoreGet<Erc20Price>,
+{
+    let mut context = BlockContext::new(stable_prices, pairs_store);
+    let eth_price = weth_price_store
+        .get_last(StoreKey::eth_usd_price_key())
+        .and_then(|price| BigDecimal::from_str(&price.price_usd).ok());
+    let mut depths = Vec::new();
+
+    for log in blk.receipts().flat_map(|view| view.receipt.logs.iter()) {
+        if !abi::pair::events::Sync::match_log(log) {
+            continue;
+        }
+        let tracked = match context.pair(&log.address) {
+            Some(tracked) => tracked,
+            None => continue,
+        };
+        let event = match abi::pair::events::Sync::decode(log) {
+            Ok(event) => event,
+            Err(_) => continue,
+        };
+
+        let reserve0 = event
+            .reserve0
+            .to_decimal(tracked.pair.token0_ref().decimals);
+        let reserve1 = event
+            .reserve1
+            .to_decimal(tracked.pair.token1_ref().decimals);
+
+        // The USD value of a reserve, from the side whose price is known
+        let reserve_usd = [
+            (tracked.token0, &tracked.base_price0, reserve0),
+            (tracked.token1, &tracked.base_price1, reserve1),
+        ]
+        .into_iter()
+        .find_map(|(token, base_price, reserve)| {
+            let price = base_price
+                .clone()
+                .or_else(|| eth_price.clone().filter(|_| token == WETH_ADDRESS_BYTES))?;
+            Some(reserve * price)
+        });
+        let reserve_usd = match reserve_usd {
+            Some(reserve_usd) if reserve_usd != BigDecimal::zero() => reserve_usd,
+            _ => continue,
+        };
+
+        depths.push(PairDepth {
+            pair_address: tracked.pair.pair_address.clone(),
+            token0: tracked.pair.token0.clone(),
+            token1: tracked.pair.token1.clone(),
+            levels: DEPTH_PERCENTS
+                .into_iter()
+                .map(|percent| {
+                    let level = depth(&reserve_usd, percent);
+                    DepthLevel {
+                        percent,
+                        up_usd: level.up_usd.to_string(),
+                        down_usd: level.down_usd.to_string(),
+                    }
+                })
+                .collect(),
+            reserve_usd: reserve_usd.to_string(),
+            block_number: blk.number,
+            ordinal: log.ordinal,
+        });
+    }
+
+    depths
+}
@@ -0,0 +1,18 @@
+use substreams::store::{StoreNew, StoreSet, StoreSetProto};
+
+use crate::{
+    pb::uniswap_pricing::v1::{PairDepth, PairDepths},
+    store_key_manager::StoreKey,
+};
+
+// Latest depth of every pair, keyed by pair address.
+#[substreams::handlers::store]
+fn store_pair_depth(depths: PairDepths, output: StoreSetProto<PairDepth>) {
+    for depth in depths.items {
+        output.set(
+            depth.ordinal,
+            StoreKey::pair_depth(&depth.pair_address),
+            &depth,
+        );
+    }
+}
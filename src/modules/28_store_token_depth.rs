@@ -0,0 +1,70 @@
+use std::str::FromStr;
+
+use substreams::{
+    pb::substreams::store_delta::Operation,
+    scalar::BigDecimal,
+    store::{DeltaProto, Deltas, StoreAdd, StoreAddBigDecimal, StoreNew},
+};
+
+use crate::{
+    pb::uniswap_pricing::v1::PairDepth, pricing::DEPTH_PERCENTS, store_key_manager::StoreKey,
+};
+
+// Depth of every token summed across its pairs, under `TokenDepth:<token>:<percent>:<up|down>`.
+// Each update of a pair adds the change from its previous depth, so every token total always
+// holds the latest depth of each of its pairs.
+#[substreams::handlers::store]
+fn store_token_depth(depth_deltas: Deltas<DeltaProto<PairDepth>>, output: StoreAddBigDecimal) {
+    add_token_depths(&depth_deltas.deltas, &output);
+}
+
+pub(crate) fn add_token_depths<S: StoreAdd<BigDecimal>>(
+    deltas: &[DeltaProto<PairDepth>],
+    output: &S,
+) {
+    for delta in deltas {
+        if delta.operation != Operation::Create && delta.operation != Operation::Update {
+            continue;
+        }
+
+        let token0 = &delta.new_value.token0_ref().address;
+        let token1 = &delta.new_value.token1_ref().address;
+
+        for percent in DEPTH_PERCENTS {
+            let (old_up, old_down) = level_usd(&delta.old_value, percent);
+            let (new_up, new_down) = level_usd(&delta.new_value, percent);
+            let up = new_up - old_up;
+            let down = new_down - old_down;
+
+            // Both reserves are worth the same, so moving either token's price by `percent`
+            // against the other takes the same amount and both tokens get the pair's levels as is
+            for token in [token0, token1] {
+                output.add(
+                    delta.ordinal,
+                    StoreKey::token_depth(token, percent, "up"),
+                    up.clone(),
+                );
+                output.add(
+                    delta.ordinal,
+                    StoreKey::token_depth(token, percent, "down"),
+                    down.clone(),
+                );
+            }
+        }
+    }
+}
+
+// Up and down depth of a pair at `percent`, zero when it has no such level, as for the old value
+// of a created key
+fn level_usd(depth: &PairDepth, percent: u32) -> (BigDecimal, BigDecimal) {
+    let parse = |value: &str| BigDecimal::from_str(value).unwrap_or_else(|_| BigDecimal::zero());
+
+    depth
+        .levels
+        .iter()
+        .find(|level| level.percent == percent)
+        .map_or_else(
+            || (BigDecimal::zero(), BigDecimal::zero()),
+            |level| (parse(&level.up_usd), parse(&level.down_usd)),
+        )
+}
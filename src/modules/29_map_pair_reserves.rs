@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use substreams::{
+    scalar::BigInt,
+    store::{StoreGet, StoreGetProto},
+};
+use substreams_ethereum::{pb::eth::v2 as eth, Event};
+
+use crate::{
+    abi,
+    block_context::{is_zero_address, BlockContext},
+    pb::uniswap_pricing::v1::{PairCreated, ReserveSnapshot, ReserveSnapshots},
+    types::stable_coin::StablePrices,
+};
+
+// Emits the raw reserves of every tracked pair on each of its `Sync` events, along with the LP
+// tokens it minted and burnt since its previous one.
+#[substreams::handlers::map]
+fn map_pair_reserves(
+    blk: eth::Block,
+    pairs_store: StoreGetProto<PairCreated>,
+) -> Result<ReserveSnapshots, substreams::errors::Error> {
+    Ok(ReserveSnapshots {
+        items: pair_reserves(&blk, &pairs_store),
+    })
+}
+
+pub(crate) fn pair_reserves<P: StoreGet<PairCreated>>(
+    blk: &eth::Block,
+    pairs_store: &P,
+) -> Vec<ReserveSnapshot> {
+    // Reserves are kept raw, so no stablecoin price is needed
+    let stable_prices = StablePrices::default();
+    let mut context = BlockContext::new(&stable_prices, pairs_store);
+    // LP tokens minted minus burnt by each contract since its latest `Sync`, from its transfers
+    // from and to the zero address
+    let mut lp_supply_changes: HashMap<&[u8], BigInt> = HashMap::new();
+    let mut snapshots = Vec::new();
+
+    for log in blk.receipts().flat_map(|view| view.receipt.logs.iter()) {
+        if let Some(transfer) = abi::pair::events::Transfer::match_and_decode(log) {
+            let minted = match (
+                is_zero_address(&transfer.from),
+                is_zero_address(&transfer.to),
+            ) {
+                (true, _) => transfer.value,
+                (false, true) => BigInt::zero() - transfer.value,
+                (false, false) => continue,
+            };
+            let change = lp_supply_changes
+                .entry(log.address.as_slice())
+                .or_insert_with(BigInt::zero);
+            *change = change.clone() + minted;
+            continue;
+        }
+        if !abi::pair::events::Sync::match_log(log) {
+            continue;
+        }
+        let tracked = match context.pair(&log.address) {
+            Some(tracked) => tracked,
+            None => continue,
+        };
+        let event = match abi::pair::events::Sync::decode(log) {
+            Ok(event) => event,
+            Err(_) => continue,
+        };
+
+        snapshots.push(ReserveSnapshot {
+            pair_address: tracked.pair.pair_address.clone(),
+            token0: tracked.pair.token0.clone(),
+            token1: tracked.pair.token1.clone(),
+            reserve0: event.reserve0.to_string(),
+            reserve1: event.reserve1.to_string(),
+            block_number: blk.number,
+            ordinal: log.ordinal,
+            lp_supply_change: lp_supply_changes
+                .remove(log.address.as_slice())
+                .unwrap_or_else(BigInt::zero)
+                .to_string(),
+        });
+    }
+
+    snapshots
+}
@@ -7,6 +7,13 @@ use crate::{
 
 #[substreams::handlers::store]
 fn store_pair_created_events(events: FactoryEvents, output: StoreSetIfNotExistsProto<PairCreated>) {
+    set_pair_created_events(events, &output);
+}
+
+pub(crate) fn set_pair_created_events<S: StoreSetIfNotExists<PairCreated>>(
+    events: FactoryEvents,
+    output: &S,
+) {
     for event in events.pair_createds {
         output.set_if_not_exists(
             event.ordinal,
@@ -0,0 +1,35 @@
+use substreams::store::{StoreNew, StoreSet, StoreSetProto};
+
+use crate::{
+    pb::uniswap_pricing::v1::{ReserveSnapshot, ReserveSnapshots},
+    store_key_manager::StoreKey,
+};
+
+// Latest reserves of every pair, under both its address and its two token addresses, so swap
+// paths given as tokens resolve without deriving pair addresses, see `quote::get_amounts_out` in
+// the `uniswap-pricing-lib` crate.
+#[substreams::handlers::store]
+fn store_pair_reserves(snapshots: ReserveSnapshots, output: StoreSetProto<ReserveSnapshot>) {
+    set_pair_reserves(snapshots, &output);
+}
+
+pub(crate) fn set_pair_reserves<S: StoreSet<ReserveSnapshot>>(
+    snapshots: ReserveSnapshots,
+    output: &S,
+) {
+    for snapshot in snapshots.items {
+        output.set(
+            snapshot.ordinal,
+            StoreKey::pair_reserves(&snapshot.pair_address),
+            &snapshot,
+        );
+        output.set(
+            snapshot.ordinal,
+            StoreKey::pair_reserves_by_tokens(
+                &snapshot.token0_ref().address,
+                &snapshot.token1_ref().address,
+            ),
+            &snapshot,
+        );
+    }
+}
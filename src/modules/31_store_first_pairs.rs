@@ -0,0 +1,27 @@
+use substreams::store::{StoreNew, StoreSetIfNotExists, StoreSetIfNotExistsProto};
+
+use crate::{
+    pb::uniswap_pricing::v1::{FactoryEvents, PairCreated},
+    store_key_manager::StoreKey,
+};
+
+// First pair created for every token, under `FirstPairByToken:<token address>`.
+#[substreams::handlers::store]
+fn store_first_pairs(events: FactoryEvents, output: StoreSetIfNotExistsProto<PairCreated>) {
+    set_first_pairs(events, &output);
+}
+
+pub(crate) fn set_first_pairs<S: StoreSetIfNotExists<PairCreated>>(
+    events: FactoryEvents,
+    output: &S,
+) {
+    for event in events.pair_createds {
+        for token in [event.token0_ref(), event.token1_ref()] {
+            output.set_if_not_exists(
+                event.ordinal,
+                StoreKey::first_pair_by_token(&token.address),
+                &event,
+            );
+        }
+    }
+}
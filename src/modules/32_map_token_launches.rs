@@ -0,0 +1,167 @@
+use std::{collections::HashSet, str::FromStr};
+
+use substreams::{
+    scalar::{BigDecimal, BigInt},
+    store::{StoreGet, StoreGetProto},
+    Hex,
+};
+use substreams_ethereum::{pb::eth::v2 as eth, Event};
+use uniswap_pricing_lib::prices::get_usd_price_bigdecimal;
+
+use crate::{
+    abi,
+    block_context::is_zero_address,
+    constants::WETH_ADDRESS,
+    pb::uniswap_pricing::v1::{
+        Erc20Price, Erc20Prices, PairCreated, StableCoinPrices, TokenLaunch, TokenLaunches,
+    },
+    store_key_manager::StoreKey,
+    types::stable_coin::StablePrices,
+};
+
+// Liquidity a pair locks by minting it to the zero address on its first mint
+const MINIMUM_LIQUIDITY: u64 = 1000;
+
+// Emits a launch for every token whose first pair receives its first liquidity. WETH and the
+// stablecoins are never launched, they are what new tokens are paired against.
+#[substreams::handlers::map]
+fn map_token_launches(
+    blk: eth::Block,
+    stable_coins: StableCoinPrices,
+    uniswap_prices: Erc20Prices,
+    pairs_store: StoreGetProto<PairCreated>,
+    first_pairs_store: StoreGetProto<PairCreated>,
+    prices_store: StoreGetProto<Erc20Price>,
+) -> Result<TokenLaunches, substreams::errors::Error> {
+    Ok(TokenLaunches {
+        items: token_launches(
+            &StablePrices::from_proto(&stable_coins),
+            &blk,
+            &uniswap_prices,
+            &pairs_store,
+            &first_pairs_store,
+            &prices_store,
+        ),
+    })
+}
+
+pub(crate) fn token_launches<P, F, U>(
+    stable_prices: &StablePrices,
+    blk: &eth::Block,
+    uniswap_prices: &Erc20Prices,
+    pairs_store: &P,
+    first_pairs_store: &F,
+    prices_store: &U,
+) -> Vec<TokenLaunch>
+where
+    P: StoreGet<PairCreated>,
+    F: StoreGet<PairCreated>,
+    U: StoreGet<Erc20Price>,
+{
+    let mut launches = Vec::new();
+
+    for view in blk.receipts() {
+        // Pairs locking `MINIMUM_LIQUIDITY` in this transaction. A pair only does so when its LP
+        // supply is zero, so the `Mint` following the lock is its first one.
+        let mut first_mints: HashSet<&[u8]> = HashSet::new();
+
+        for log in view.receipt.logs.iter() {
+            if let Some(transfer) = abi::pair::events::Transfer::match_and_decode(log) {
+                if is_zero_address(&transfer.from)
+                    && is_zero_address(&transfer.to)
+                    && transfer.value == BigInt::from(MINIMUM_LIQUIDITY)
+                {
+                    first_mints.insert(log.address.as_slice());
+                }
+                continue;
+            }
+            let mint = match abi::pair::events::Mint::match_and_decode(log) {
+                Some(mint) => mint,
+                None => continue,
+            };
+            if !first_mints.remove(log.address.as_slice()) {
+                continue;
+            }
+            let pair = match pairs_store.get_last(StoreKey::pair_key(&Hex::encode(&log.address))) {
+                Some(pair) => pair,
+                None => continue,
+            };
+
+            let sides = [
+                (
+                    pair.token0_ref(),
+                    pair.token1_ref(),
+                    &mint.amount0,
+                    &mint.amount1,
+                ),
+                (
+                    pair.token1_ref(),
+                    pair.token0_ref(),
+                    &mint.amount1,
+                    &mint.amount0,
+                ),
+            ];
+            for (token, paired_token, token_amount, paired_token_amount) in sides {
+                if token.address == WETH_ADDRESS || stable_prices.contains(&token.address) {
+                    continue;
+                }
+                // Only the token's first pair launches it
+                let is_first_pair = first_pairs_store
+                    .get_last(StoreKey::first_pair_by_token(&token.address))
+                    .map_or(false, |first| first.pair_address == pair.pair_address);
+                if !is_first_pair {
+                    continue;
+                }
+
+                let paired_token_amount = paired_token_amount.to_decimal(paired_token.decimals);
+                let initial_liquidity_usd = price_at(
+                    uniswap_prices,
+                    prices_store,
+                    &paired_token.address,
+                    log.ordinal,
+                )
+                .map(|price| paired_token_amount.clone() * price * BigDecimal::from(2));
+
+                launches.push(TokenLaunch {
+                    token: Some(token.clone()),
+                    pair: Some(pair.clone()),
+                    token_amount: token_amount.to_decimal(token.decimals).to_string(),
+                    paired_token_amount: paired_token_amount.to_string(),
+                    initial_liquidity_usd: initial_liquidity_usd
+                        .map_or_else(String::new, |liquidity| liquidity.to_string()),
+                    first_price: uniswap_prices
+                        .items
+                        .iter()
+                        .find(|price| price.token_ref().address == token.address)
+                        .cloned(),
+                    mint_tx_hash: Hex::encode(&view.transaction.hash),
+                    block_number: blk.number,
+                    ordinal: log.ordinal,
+                    block_time: Some(blk.timestamp().to_owned()),
+                });
+            }
+        }
+    }
+
+    launches
+}
+
+// USD price of `token` at `ordinal`: its latest price in the block up to `ordinal`, else the stored
+// one. The store holds the prices as of the end of the block, so that fallback is a later price
+// when the token is only priced after `ordinal` in the block.
+fn price_at<U: StoreGet<Erc20Price>>(
+    uniswap_prices: &Erc20Prices,
+    prices_store: &U,
+    token: &str,
+    ordinal: u64,
+) -> Option<BigDecimal> {
+    uniswap_prices
+        .items
+        .iter()
+        .filter(|price| price.token_ref().address == token && price.ordinal <= ordinal)
+        .last()
+        .map_or_else(
+            || get_usd_price_bigdecimal(prices_store, token),
+            |price| BigDecimal::from_str(&price.price_usd).ok(),
+        )
+}
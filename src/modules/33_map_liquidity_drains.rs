@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+
+use substreams::{
+    scalar::{BigDecimal, BigInt},
+    store::{DeltaProto, Deltas, StoreGet, StoreGetBigInt, StoreGetProto},
+    Hex,
+};
+use substreams_ethereum::{pb::eth::v2 as eth, Event};
+use uniswap_pricing_lib::prices::{get_usd_price, get_usd_price_bigdecimal};
+
+use crate::{
+    abi,
+    block_context::is_zero_address,
+    constants::WETH_ADDRESS,
+    params::Params,
+    pb::uniswap_pricing::v1::{
+        Erc20Price, Erc20Token, LiquidityDrain, LiquidityDrains, ReserveSnapshot, StableCoinPrices,
+    },
+    store_key_manager::StoreKey,
+    types::{
+        reserve_change::{constant_product, reserves, ReserveChange, WINDOW_GRIDS},
+        stable_coin::StablePrices,
+    },
+};
+
+// Default share of its liquidity a pair may lose before its tokens are flagged.
+const DEFAULT_MAX_DROP_PERCENT: f64 = 50.0;
+
+// Reports the pairs that lost at least `max_drop_percent` of their liquidity, or of their WETH or
+// stablecoin reserve, since the start of the block or of their current window in
+// `store_liquidity_windows`, whichever held the most. Liquidity is measured as
+// sqrt(reserve0 * reserve1), which swaps never lower, while the WETH or stablecoin reserve also
+// catches tokens dumped into the pair. A token of a drained pair is flagged when it is neither WETH
+// nor a stablecoin and its price in `store_uniswap_prices` comes from that pair.
+// Params: `max_drop_percent=<percent>`
+#[substreams::handlers::map]
+fn map_liquidity_drains(
+    params: String,
+    blk: eth::Block,
+    stable_coins: StableCoinPrices,
+    reserve_deltas: Deltas<DeltaProto<ReserveSnapshot>>,
+    windows_store: StoreGetProto<ReserveSnapshot>,
+    lp_supply_store: StoreGetBigInt,
+    prices_store: StoreGetProto<Erc20Price>,
+) -> Result<LiquidityDrains, substreams::errors::Error> {
+    let max_drop_percent =
+        Params::parse(&params).get_or("max_drop_percent", DEFAULT_MAX_DROP_PERCENT)?;
+
+    Ok(LiquidityDrains {
+        items: liquidity_drains(
+            &StablePrices::from_proto(&stable_coins),
+            max_drop_percent,
+            &blk,
+            &ReserveChange::from_deltas(reserve_deltas.deltas),
+            &windows_store,
+            &lp_supply_store,
+            &prices_store,
+        ),
+    })
+}
+
+pub(crate) fn liquidity_drains<W, L, U>(
+    stable_prices: &StablePrices,
+    max_drop_percent: f64,
+    blk: &eth::Block,
+    changes: &[ReserveChange],
+    windows_store: &W,
+    lp_supply_store: &L,
+    prices_store: &U,
+) -> Vec<LiquidityDrain>
+where
+    W: StoreGet<ReserveSnapshot>,
+    L: StoreGet<BigInt>,
+    U: StoreGet<Erc20Price>,
+{
+    let removals = liquidity_removals(blk);
+    let mut drains = Vec::new();
+
+    // Pairs created in the block have no reserves before it
+    for change in changes.iter().filter(|change| !change.created) {
+        let pair_address = &change.after.pair_address;
+        let token0 = change.after.token0_ref();
+        let token1 = change.after.token1_ref();
+        let is_quote = |token: &Erc20Token| {
+            token.address == WETH_ADDRESS || stable_prices.contains(&token.address)
+        };
+
+        let mut starts = vec![change.before.clone()];
+        for grid in WINDOW_GRIDS {
+            starts.extend(windows_store.get_last(StoreKey::liquidity_window(pair_address, grid)));
+        }
+
+        let reference_liquidity = starts
+            .iter()
+            .map(|start| liquidity(&constant_product(start)))
+            .fold(0.0, f64::max);
+        if reference_liquidity <= 0.0 {
+            continue;
+        }
+        let drop_percent =
+            (1.0 - liquidity(&constant_product(&change.after)) / reference_liquidity) * 100.0;
+
+        // The WETH or stablecoin side, when the other token is neither
+        let quote = match (is_quote(token0), is_quote(token1)) {
+            (true, false) => Some((token0, 0)),
+            (false, true) => Some((token1, 1)),
+            _ => None,
+        };
+        let quote_drop = quote.and_then(|(token, side)| {
+            let reserve = |snapshot: &ReserveSnapshot| {
+                let (reserve0, reserve1) = reserves(snapshot);
+                if side == 0 {
+                    reserve0
+                } else {
+                    reserve1
+                }
+            };
+            let reference = starts
+                .iter()
+                .map(reserve)
+                .fold(BigDecimal::zero(), |max, reserve| {
+                    if reserve > max {
+                        reserve
+                    } else {
+                        max
+                    }
+                });
+            if reference <= BigDecimal::zero() {
+                return None;
+            }
+
+            let lost = reference.clone() - reserve(&change.after);
+            let percent = to_f64(&lost) / to_f64(&reference) * 100.0;
+            let price = if token.address == WETH_ADDRESS {
+                get_usd_price_bigdecimal(prices_store, WETH_ADDRESS)
+            } else {
+                stable_prices.usd_price(&token.address)
+            };
+            Some((percent, price.map(|price| lost * price)))
+        });
+
+        let quote_drop_percent = quote_drop.as_ref().map_or(0.0, |(percent, _)| *percent);
+        if drop_percent < max_drop_percent && quote_drop_percent < max_drop_percent {
+            continue;
+        }
+
+        // LP tokens burnt in the block by each holder, against the supply before the block
+        let mut removed: HashMap<&str, BigInt> = HashMap::new();
+        for removal in removals
+            .iter()
+            .filter(|removal| removal.pair == *pair_address)
+        {
+            let total = removed
+                .entry(removal.remover.as_str())
+                .or_insert_with(BigInt::zero);
+            *total = total.clone() + removal.liquidity.clone();
+        }
+        let top_remover = removed
+            .into_iter()
+            .max_by(|(_, a), (_, b)| to_f64(a).total_cmp(&to_f64(b)));
+        let lp_supply = lp_supply_store
+            .get_last(StoreKey::lp_supply(pair_address))
+            .unwrap_or_else(BigInt::zero)
+            - change.lp_supply_change.clone();
+
+        let reserve = |value: &str, decimals: u64| {
+            value.parse::<BigInt>().map_or_else(
+                |_| String::new(),
+                |value| value.to_decimal(decimals).to_string(),
+            )
+        };
+
+        drains.push(LiquidityDrain {
+            pair_address: pair_address.clone(),
+            token0: change.after.token0.clone(),
+            token1: change.after.token1.clone(),
+            flagged_tokens: [token0, token1]
+                .into_iter()
+                .filter(|token| !is_quote(*token))
+                .filter(|token| {
+                    get_usd_price(prices_store, &token.address)
+                        .map_or(false, |price| price.pair_address == *pair_address)
+                })
+                .cloned()
+                .collect(),
+            drop_percent: drop_percent.to_string(),
+            reserve0: reserve(&change.after.reserve0, token0.decimals),
+            reserve1: reserve(&change.after.reserve1, token1.decimals),
+            top_remover: top_remover
+                .as_ref()
+                .map_or_else(String::new, |(remover, _)| remover.to_string()),
+            top_remover_percent: top_remover
+                .filter(|_| lp_supply > BigInt::zero())
+                .map_or_else(String::new, |(_, removed)| {
+                    (to_f64(&removed) / to_f64(&lp_supply) * 100.0).to_string()
+                }),
+            block_number: blk.number,
+            ordinal: change.after.ordinal,
+            block_time: Some(blk.timestamp().to_owned()),
+            quote_drop_percent: quote_drop
+                .as_ref()
+                .map_or_else(String::new, |(percent, _)| percent.to_string()),
+            quote_drop_usd: quote_drop
+                .and_then(|(_, usd)| usd)
+                .map_or_else(String::new, |usd| usd.to_string()),
+        });
+    }
+
+    drains
+}
+
+// LP tokens burnt from a pair on behalf of their holder
+struct LiquidityRemoval {
+    pair: String,
+    remover: String,
+    liquidity: BigInt,
+}
+
+// Every `Burn` of the block, attributed to the holder who sent the LP tokens to the pair earlier in
+// the same transaction. Routers often burn to themselves, e.g. to unwrap WETH, so the `to` of the
+// `Burn` is only used when no such transfer is found. The amount burnt is the pair's transfer of
+// its own LP tokens to the zero address right before the `Burn`.
+fn liquidity_removals(blk: &eth::Block) -> Vec<LiquidityRemoval> {
+    let mut removals = Vec::new();
+
+    for view in blk.receipts() {
+        let mut holders: HashMap<&[u8], Vec<u8>> = HashMap::new();
+        let mut burnt: HashMap<&[u8], BigInt> = HashMap::new();
+
+        for log in view.receipt.logs.iter() {
+            if let Some(transfer) = abi::pair::events::Transfer::match_and_decode(log) {
+                if transfer.to == log.address {
+                    holders.insert(log.address.as_slice(), transfer.from);
+                } else if transfer.from == log.address && is_zero_address(&transfer.to) {
+                    burnt.insert(log.address.as_slice(), transfer.value);
+                }
+                continue;
+            }
+            let burn = match abi::pair::events::Burn::match_and_decode(log) {
+                Some(burn) => burn,
+                None => continue,
+            };
+
+            let remover = holders.remove(log.address.as_slice()).unwrap_or(burn.to);
+            removals.push(LiquidityRemoval {
+                pair: Hex::encode(&log.address),
+                remover: Hex::encode(remover),
+                liquidity: burnt
+                    .remove(log.address.as_slice())
+                    .unwrap_or_else(BigInt::zero),
+            });
+        }
+    }
+
+    removals
+}
+
+// Square root of a constant product, as a float
+fn liquidity(product: &BigDecimal) -> f64 {
+    to_f64(product).sqrt()
+}
+
+fn to_f64(value: &impl ToString) -> f64 {
+    value.to_string().parse::<f64>().unwrap_or(0.0)
+}
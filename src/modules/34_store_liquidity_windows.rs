@@ -0,0 +1,32 @@
+use substreams::store::{StoreNew, StoreSet, StoreSetProto};
+
+use crate::{
+    pb::uniswap_pricing::v1::{LiquidityWindows, ReserveSnapshot},
+    store_key_manager::StoreKey,
+};
+
+// Reserves of every pair at the start of its current window on each grid, under
+// `LiquidityWindow:<pair>:<grid>`, so `map_liquidity_drains` catches drains spread over several
+// blocks. Entering a window overwrites the previous one, so a pair never holds more than two keys.
+#[substreams::handlers::store]
+fn store_liquidity_windows(windows: LiquidityWindows, output: StoreSetProto<ReserveSnapshot>) {
+    set_liquidity_windows(windows, &output);
+}
+
+pub(crate) fn set_liquidity_windows<S: StoreSet<ReserveSnapshot>>(
+    windows: LiquidityWindows,
+    output: &S,
+) {
+    for window in windows.items {
+        let start = match window.start {
+            Some(start) => start,
+            None => continue,
+        };
+
+        output.set(
+            window.ordinal,
+            StoreKey::liquidity_window(&start.pair_address, window.grid),
+            &start,
+        );
+    }
+}
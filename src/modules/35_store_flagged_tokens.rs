@@ -0,0 +1,26 @@
+use substreams::store::{StoreNew, StoreSet, StoreSetProto};
+
+use crate::{
+    pb::uniswap_pricing::v1::{LiquidityDrain, LiquidityDrains},
+    store_key_manager::StoreKey,
+};
+
+// Latest liquidity drain of every flagged token, under `FlaggedToken:<token address>`. Consumers
+// stop trusting the price of a token once it is here, see `prices::get_trusted_usd_price` in the
+// `uniswap-pricing-lib` crate.
+#[substreams::handlers::store]
+fn store_flagged_tokens(drains: LiquidityDrains, output: StoreSetProto<LiquidityDrain>) {
+    set_flagged_tokens(drains, &output);
+}
+
+pub(crate) fn set_flagged_tokens<S: StoreSet<LiquidityDrain>>(drains: LiquidityDrains, output: &S) {
+    for drain in drains.items {
+        for token in &drain.flagged_tokens {
+            output.set(
+                drain.ordinal,
+                StoreKey::flagged_token(&token.address),
+                &drain,
+            );
+        }
+    }
+}
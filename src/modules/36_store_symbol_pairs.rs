@@ -0,0 +1,58 @@
+use std::str::FromStr;
+
+use substreams::{
+    pb::substreams::store_delta::Operation,
+    scalar::BigDecimal,
+    store::{DeltaProto, Deltas, StoreAppend, StoreNew},
+};
+
+use crate::{
+    pb::uniswap_pricing::v1::{TokenLiquidities, TokenLiquidity},
+    store_key_manager::StoreKey,
+};
+
+// Pairs of every symbol that reached `min_liquidity_usd`, under `LiquidPairsBySymbol:<symbol>`.
+// A pair is appended each time it rises back above the minimum, so readers dedupe the list.
+#[substreams::handlers::store]
+fn store_symbol_pairs(
+    liquidities: TokenLiquidities,
+    pair_liquidity_deltas: Deltas<DeltaProto<TokenLiquidity>>,
+    output: StoreAppend<String>,
+) {
+    let min_liquidity_usd = match BigDecimal::from_str(&liquidities.min_liquidity_usd) {
+        Ok(min_liquidity_usd) => min_liquidity_usd,
+        Err(_) => return,
+    };
+
+    for (ordinal, key, pair_address) in
+        liquid_symbol_pairs(&min_liquidity_usd, pair_liquidity_deltas.deltas)
+    {
+        output.append(ordinal, key, pair_address);
+    }
+}
+
+// Ordinal, key and pair address of every pair that rose to `min_liquidity_usd` in the deltas
+pub(crate) fn liquid_symbol_pairs(
+    min_liquidity_usd: &BigDecimal,
+    deltas: Vec<DeltaProto<TokenLiquidity>>,
+) -> Vec<(u64, String, String)> {
+    let is_liquid = |liquidity: &TokenLiquidity| {
+        BigDecimal::from_str(&liquidity.liquidity_usd)
+            .map_or(false, |liquidity_usd| liquidity_usd >= *min_liquidity_usd)
+    };
+
+    deltas
+        .into_iter()
+        .filter(|delta| {
+            is_liquid(&delta.new_value)
+                && (delta.operation == Operation::Create || !is_liquid(&delta.old_value))
+        })
+        .map(|delta| {
+            (
+                delta.ordinal,
+                StoreKey::liquid_pairs_by_symbol(&delta.new_value.token_ref().symbol),
+                delta.new_value.pair_address,
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,101 @@
+use std::str::FromStr;
+
+use substreams::{
+    scalar::BigDecimal,
+    store::{StoreGet, StoreGetArray, StoreGetProto},
+};
+
+use crate::{
+    pb::uniswap_pricing::v1::{
+        Erc20Token, SymbolLeader, SymbolLeaders, TokenLiquidities, TokenLiquidity,
+    },
+    store_key_manager::StoreKey,
+};
+
+// Token leading each symbol whose pairs were updated in the block. A token's strength is the
+// current liquidity of all its pairs that ever reached `min_liquidity_usd`, and it only leads
+// when that is above the minimum and beats every other token of the symbol by
+// `takeover_margin_percent`.
+#[substreams::handlers::map]
+fn map_symbol_leaders(
+    liquidities: TokenLiquidities,
+    pair_liquidity_store: StoreGetProto<TokenLiquidity>,
+    symbol_pairs_store: StoreGetArray<String>,
+) -> Result<SymbolLeaders, substreams::errors::Error> {
+    Ok(SymbolLeaders {
+        items: symbol_leaders(&liquidities, &pair_liquidity_store, &symbol_pairs_store),
+    })
+}
+
+pub(crate) fn symbol_leaders<L, P>(
+    liquidities: &TokenLiquidities,
+    pair_liquidity_store: &L,
+    symbol_pairs_store: &P,
+) -> Vec<SymbolLeader>
+where
+    L: StoreGet<TokenLiquidity>,
+    P: StoreGet<Vec<String>>,
+{
+    let parse = |value: &str| BigDecimal::from_str(value).unwrap_or_else(|_| BigDecimal::zero());
+    let min_liquidity_usd = parse(&liquidities.min_liquidity_usd);
+    let margin = BigDecimal::from(100) + parse(&liquidities.takeover_margin_percent);
+
+    // Every updated symbol once, at the ordinal of its last update in the block
+    let mut symbols: Vec<(&str, u64, u64)> = Vec::new();
+    for liquidity in &liquidities.items {
+        let symbol = liquidity.token_ref().symbol.as_str();
+        match symbols.iter_mut().find(|(known, _, _)| *known == symbol) {
+            Some(entry) => entry.2 = liquidity.ordinal,
+            None => symbols.push((symbol, liquidity.block_number, liquidity.ordinal)),
+        }
+    }
+
+    symbols
+        .into_iter()
+        .filter_map(|(symbol, block_number, ordinal)| {
+            let mut pairs =
+                symbol_pairs_store.get_last(StoreKey::liquid_pairs_by_symbol(symbol))?;
+            pairs.sort();
+            pairs.dedup();
+
+            // Liquidity of every token of the symbol, strongest first
+            let mut tokens: Vec<(Erc20Token, BigDecimal)> = Vec::new();
+            for pair in pairs
+                .iter()
+                .filter_map(|pair| pair_liquidity_store.get_last(StoreKey::pair_liquidity(pair)))
+            {
+                let liquidity_usd = parse(&pair.liquidity_usd);
+                let token = pair.token_ref();
+                match tokens
+                    .iter_mut()
+                    .find(|(known, _)| known.address == token.address)
+                {
+                    Some(entry) => entry.1 = entry.1.clone() + liquidity_usd,
+                    None => tokens.push((token.clone(), liquidity_usd)),
+                }
+            }
+            tokens.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+            let leads = match tokens.as_slice() {
+                [] => false,
+                [(_, top)] => *top >= min_liquidity_usd,
+                [(_, top), (_, runner_up), ..] => {
+                    *top >= min_liquidity_usd
+                        && top.clone() * BigDecimal::from(100) >= runner_up.clone() * margin.clone()
+                }
+            };
+
+            let mut tokens = tokens.into_iter().map(|(token, _)| token);
+            let leader = if leads { tokens.next() } else { None };
+
+            Some(SymbolLeader {
+                symbol: symbol.to_string(),
+                leader,
+                other_tokens: tokens.map(|token| token.address).collect(),
+                block_number,
+                ordinal,
+                takeover_blocks: liquidities.takeover_blocks,
+            })
+        })
+        .collect()
+}
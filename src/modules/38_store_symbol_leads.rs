@@ -0,0 +1,29 @@
+use substreams::store::{StoreDelete, StoreNew, StoreSetIfNotExists, StoreSetIfNotExistsInt64};
+
+use crate::{pb::uniswap_pricing::v1::SymbolLeaders, store_key_manager::StoreKey};
+
+// Block each symbol leader started leading at, under `SymbolLeadSince:<token address>`. The key
+// of every other token of the symbol is deleted, so a lead restarts once it is lost.
+#[substreams::handlers::store]
+fn store_symbol_leads(leaders: SymbolLeaders, output: StoreSetIfNotExistsInt64) {
+    set_symbol_leads(leaders, &output);
+}
+
+pub(crate) fn set_symbol_leads<S: StoreSetIfNotExists<i64> + StoreDelete>(
+    leaders: SymbolLeaders,
+    output: &S,
+) {
+    for leader in leaders.items {
+        for token in &leader.other_tokens {
+            output.delete_prefix(leader.ordinal as i64, &StoreKey::symbol_lead_since(token));
+        }
+
+        if let Some(token) = &leader.leader {
+            output.set_if_not_exists(
+                leader.ordinal,
+                StoreKey::symbol_lead_since(&token.address),
+                &(leader.block_number as i64),
+            );
+        }
+    }
+}
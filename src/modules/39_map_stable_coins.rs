@@ -0,0 +1,36 @@
+use substreams::{pb::substreams::Clock, store::StoreGetProto};
+
+use crate::{
+    params::Params,
+    pb::{chainlink::v1::Price, uniswap_pricing::v1::StableCoinPrices},
+    types::stable_coin::StableCoins,
+};
+
+// Default maximum age of a stablecoin's Chainlink answer, the heartbeat of the mainnet USD
+// stablecoin feeds.
+const DEFAULT_STABLECOIN_HEARTBEAT_SECONDS: i64 = 86400;
+
+// Resolves the stablecoin set once, with the USD value of each coin at the end of the block, for
+// every module pricing against stablecoins.
+// Params: `stablecoins=<entry>,<entry>,...`, see `StableCoin::parse` for the entry format
+//         `stablecoin_heartbeat_seconds=<seconds>`, after which a Chainlink answer is stale
+#[substreams::handlers::map]
+fn map_stable_coins(
+    params: String,
+    clock: Clock,
+    chainlink_answers_store: StoreGetProto<Price>,
+) -> Result<StableCoinPrices, substreams::errors::Error> {
+    let params = Params::parse(&params);
+    let stable_coins = StableCoins::from_params(&params)?;
+    let heartbeat_seconds = params.get_or(
+        "stablecoin_heartbeat_seconds",
+        DEFAULT_STABLECOIN_HEARTBEAT_SECONDS,
+    )?;
+
+    let block_seconds = clock
+        .timestamp
+        .as_ref()
+        .map_or(0, |timestamp| timestamp.seconds);
+
+    Ok(stable_coins.prices(&chainlink_answers_store, block_seconds, heartbeat_seconds))
+}
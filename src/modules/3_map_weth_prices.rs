@@ -1,61 +1,83 @@
-use substreams::{
-    store::{StoreGet, StoreGetProto},
-    Hex,
-};
-use substreams_ethereum::{pb::eth::v2 as eth, Event};
+use substreams::store::{StoreGet, StoreGetProto};
+use substreams_ethereum::pb::eth::v2 as eth;
 
 use crate::{
     abi,
-    constants::{STABLE_COINS, WETH_ADDRESS},
-    pb::uniswap_pricing::v1::{erc20_price::Source, Erc20Price, Erc20Prices, PairCreated},
-    store_key_manager::StoreKey,
+    block_context::BlockContext,
+    constants::WETH_ADDRESS_BYTES,
+    pb::uniswap_pricing::v1::{
+        erc20_price::Source, Erc20Price, Erc20Prices, PairCreated, StableCoinPrices,
+    },
+    pricing::{price_pair, PricingInput},
+    types::stable_coin::StablePrices,
 };
 
 #[substreams::handlers::map]
 fn map_weth_prices(
     blk: eth::Block,
+    stable_coins: StableCoinPrices,
     pairs_store: StoreGetProto<PairCreated>,
 ) -> Result<Erc20Prices, substreams::errors::Error> {
-    let prices: Vec<Erc20Price> = blk
-        .receipts()
-        .flat_map(|view| {
-            view.receipt.logs.iter().filter_map(|log| {
-                if let Some(event) = abi::pair::events::Sync::match_and_decode(log) {
-                    if let Some(pair) =
-                        pairs_store.get_last(StoreKey::pair_key(&Hex::encode(&log.address)))
-                    {
-                        let reserve0 = event.reserve0.to_decimal(pair.token0_ref().decimals);
-                        let reserve1 = event.reserve1.to_decimal(pair.token1_ref().decimals);
+    Ok(Erc20Prices {
+        items: weth_prices(&StablePrices::from_proto(&stable_coins), &blk, &pairs_store),
+    })
+}
+
+pub(crate) fn weth_prices<P: StoreGet<PairCreated>>(
+    stable_prices: &StablePrices,
+    blk: &eth::Block,
+    pairs_store: &P,
+) -> Vec<Erc20Price> {
+    let mut context = BlockContext::new(stable_prices, pairs_store);
+    let mut prices = Vec::new();
 
-                        if pair.token0_ref().address == WETH_ADDRESS
-                            && STABLE_COINS.contains(&pair.token1_ref().address.as_str())
-                        {
-                            let weth_price = reserve1.clone() / reserve0.clone();
-                            return Some(Erc20Price {
-                                token: pair.token0.clone(), // WETH
-                                price_usd: weth_price.to_string(),
-                                block_number: blk.number,
-                                ordinal: log.ordinal,
-                                source: Source::Uniswap as i32,
-                            });
-                        } else if pair.token1_ref().address == WETH_ADDRESS
-                            && STABLE_COINS.contains(&pair.token0_ref().address.as_str())
-                        {
-                            let weth_price = reserve0.clone() / reserve1.clone();
-                            return Some(Erc20Price {
-                                token: pair.token1.clone(), // WETH
-                                price_usd: weth_price.to_string(),
-                                block_number: blk.number,
-                                ordinal: log.ordinal,
-                                source: Source::Uniswap as i32,
-                            });
-                        }
-                    }
-                }
-                None
-            })
+    for log in blk.receipts().flat_map(|view| view.receipt.logs.iter()) {
+        if !abi::pair::events::Sync::match_log(log) {
+            continue;
+        }
+        let tracked = match context.pair(&log.address) {
+            Some(tracked) => tracked,
+            None => continue,
+        };
+        if tracked.token0 != WETH_ADDRESS_BYTES && tracked.token1 != WETH_ADDRESS_BYTES {
+            continue;
+        }
+        let event = match abi::pair::events::Sync::decode(log) {
+            Ok(event) => event,
+            Err(_) => continue,
+        };
+
+        let weth_price = price_pair(PricingInput {
+            token0: tracked.token0,
+            token1: tracked.token1,
+            reserve0: event.reserve0,
+            reserve1: event.reserve1,
+            decimals0: tracked.pair.token0_ref().decimals,
+            decimals1: tracked.pair.token1_ref().decimals,
+            base_price0: tracked.base_price0.clone(),
+            base_price1: tracked.base_price1.clone(),
+            eth_price: None,
         })
-        .collect();
+        .into_iter()
+        .filter(|price| price.side.select(tracked.token0, tracked.token1) == WETH_ADDRESS_BYTES)
+        .find_map(|price| Some((price.side, price.price_usd?)));
+
+        if let Some((side, price_usd)) = weth_price {
+            prices.push(Erc20Price {
+                token: side
+                    .select(&tracked.pair.token0, &tracked.pair.token1)
+                    .clone(),
+                price_usd: price_usd.to_string(),
+                price_eth: String::from("1"),
+                block_number: blk.number,
+                ordinal: log.ordinal,
+                source: Source::Uniswap as i32,
+                eth_price_source: Source::Uniswap as i32,
+                block_time: Some(blk.timestamp().to_owned()),
+                pair_address: tracked.pair.pair_address.clone(),
+            });
+        }
+    }
 
-    Ok(Erc20Prices { items: prices })
+    prices
 }
@@ -0,0 +1,65 @@
+use substreams::{
+    errors::Error,
+    store::{DeltaProto, Deltas},
+};
+
+use crate::{
+    params::Params,
+    pb::uniswap_pricing::v1::{LiquidityWindow, LiquidityWindows, ReserveSnapshot},
+    types::reserve_change::{liquidity_window, ReserveChange, DEFAULT_WINDOW_BLOCKS, WINDOW_GRIDS},
+};
+
+// Emits the reserves of every pair entering a new window of `window_blocks` blocks, on each of the
+// two window grids, for `store_liquidity_windows`. The window starts with the reserves the pair
+// held before the block, or with the ones after it when the pair got its first reserves in the
+// block.
+// Params: `window_blocks=<blocks>`
+#[substreams::handlers::map]
+fn map_liquidity_windows(
+    params: String,
+    reserve_deltas: Deltas<DeltaProto<ReserveSnapshot>>,
+) -> Result<LiquidityWindows, Error> {
+    let window_blocks = Params::parse(&params).get_or("window_blocks", DEFAULT_WINDOW_BLOCKS)?;
+    if window_blocks == 0 {
+        return Err(Error::Unexpected(
+            "invalid value for param `window_blocks`: 0".to_string(),
+        ));
+    }
+
+    Ok(LiquidityWindows {
+        items: liquidity_windows(
+            window_blocks,
+            &ReserveChange::from_deltas(reserve_deltas.deltas),
+        ),
+    })
+}
+
+pub(crate) fn liquidity_windows(
+    window_blocks: u64,
+    changes: &[ReserveChange],
+) -> Vec<LiquidityWindow> {
+    let mut windows = Vec::new();
+
+    for change in changes {
+        for grid in WINDOW_GRIDS {
+            let window = |snapshot: &ReserveSnapshot| {
+                liquidity_window(snapshot.block_number, window_blocks, grid)
+            };
+            let start = if change.created {
+                &change.after
+            } else if window(&change.before) < window(&change.after) {
+                &change.before
+            } else {
+                continue;
+            };
+
+            windows.push(LiquidityWindow {
+                grid,
+                start: Some(start.clone()),
+                ordinal: change.after.ordinal,
+            });
+        }
+    }
+
+    windows
+}
@@ -0,0 +1,31 @@
+use std::str::FromStr;
+
+use substreams::{
+    scalar::BigInt,
+    store::{StoreAdd, StoreAddBigInt, StoreNew},
+};
+
+use crate::{pb::uniswap_pricing::v1::ReserveSnapshots, store_key_manager::StoreKey};
+
+// LP token supply of every pair, under `LpSupply:<pair address>`, summed from the LP tokens each
+// of its `Sync` events reports minted and burnt.
+#[substreams::handlers::store]
+fn store_lp_supply(snapshots: ReserveSnapshots, output: StoreAddBigInt) {
+    add_lp_supply(snapshots, &output);
+}
+
+pub(crate) fn add_lp_supply<S: StoreAdd<BigInt>>(snapshots: ReserveSnapshots, output: &S) {
+    for snapshot in snapshots.items {
+        let change =
+            BigInt::from_str(&snapshot.lp_supply_change).unwrap_or_else(|_| BigInt::zero());
+        if change == BigInt::zero() {
+            continue;
+        }
+
+        output.add(
+            snapshot.ordinal,
+            StoreKey::lp_supply(&snapshot.pair_address),
+            change,
+        );
+    }
+}
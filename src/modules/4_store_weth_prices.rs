@@ -7,6 +7,10 @@ use crate::{
 
 #[substreams::handlers::store]
 fn store_weth_prices(prices: Erc20Prices, output: StoreSetProto<Erc20Price>) {
+    set_weth_prices(prices, &output);
+}
+
+pub(crate) fn set_weth_prices<S: StoreSet<Erc20Price>>(prices: Erc20Prices, output: &S) {
     for price in prices.items {
         output.set(price.ordinal, StoreKey::eth_usd_price_key(), &price);
     }
@@ -1,113 +1,176 @@
-use std::str::FromStr;
+use std::{collections::HashSet, str::FromStr};
 
 use substreams::{
     scalar::BigDecimal,
-    store::{StoreGet, StoreGetBigDecimal, StoreGetProto},
-    Hex,
+    store::{StoreGet, StoreGetProto},
 };
-use substreams_ethereum::{pb::eth::v2 as eth, Event};
+use substreams_ethereum::pb::eth::v2 as eth;
 
 use crate::{
     abi,
-    constants::{STABLE_COINS, WETH_ADDRESS},
-    pb::uniswap_pricing::v1::{erc20_price::Source, Erc20Price, Erc20Prices, PairCreated},
+    block_context::BlockContext,
+    chainlink_feeds::{fresh_usd_answer, DEFAULT_ETH_USD_HEARTBEAT_SECONDS},
+    params::Params,
+    pb::{
+        chainlink::v1::Price,
+        uniswap_pricing::v1::{
+            erc20_price::Source, Erc20Price, Erc20Prices, PairCreated, StableCoinPrices,
+        },
+    },
+    pricing::{price_pair_with_anchors, Anchor, PricingInput},
     store_key_manager::StoreKey,
+    types::stable_coin::StablePrices,
 };
 
+// Params: `eth_usd_heartbeat_seconds=<seconds>`, after which the Chainlink ETH/USD answer is stale
 #[substreams::handlers::map]
 fn map_uniswap_prices(
+    params: String,
     blk: eth::Block,
+    stable_coins: StableCoinPrices,
     pairs_store: StoreGetProto<PairCreated>,
     weth_price_store: StoreGetProto<Erc20Price>,
-    chainlink_prices_store: StoreGetBigDecimal,
+    chainlink_answers_store: StoreGetProto<Price>,
 ) -> Result<Erc20Prices, substreams::errors::Error> {
-    let prices: Vec<Erc20Price> = blk
-        .receipts()
-        .flat_map(|view| {
-            view.receipt.logs.iter().filter_map(|log| {
-                if let Some(event) = abi::pair::events::Sync::match_and_decode(log) {
-                    // Get Sync events that have a related Pair in the store
-                    if let Some(pair) =
-                        pairs_store.get_last(StoreKey::pair_key(&Hex::encode(&log.address)))
-                    {
-                        let reserve0 = event.reserve0.to_decimal(pair.token0_ref().decimals);
-                        let reserve1 = event.reserve1.to_decimal(pair.token1_ref().decimals);
+    let params = Params::parse(&params);
+    let heartbeat_seconds = params.get_or(
+        "eth_usd_heartbeat_seconds",
+        DEFAULT_ETH_USD_HEARTBEAT_SECONDS,
+    )?;
 
-                        if reserve0.eq(&BigDecimal::zero()) || reserve1.eq(&BigDecimal::zero()) {
-                            return None;
-                        }
-                        let mut prices = Vec::new();
-                        let eth_price = fetch_eth_price(&chainlink_prices_store, &weth_price_store);
+    Ok(Erc20Prices {
+        items: uniswap_prices(
+            &StablePrices::from_proto(&stable_coins),
+            heartbeat_seconds,
+            &blk,
+            &pairs_store,
+            &weth_price_store,
+            &chainlink_answers_store,
+        ),
+    })
+}
+
+pub(crate) fn uniswap_prices<P, W, A>(
+    stable_prices: &StablePrices,
+    heartbeat_seconds: i64,
+    blk: &eth::Block,
+    pairs_store: &P,
+    weth_price_store: &W,
+    chainlink_answers_store: &A,
+) -> Vec<Erc20Price>
+where
+    P: StoreGet<PairCreated>,
+    W: StoreGet<Erc20Price>,
+    A: StoreGet<Price>,
+{
+    let mut context = BlockContext::new(stable_prices, pairs_store);
+    // Store reads see the state at the end of the block, so the ETH price is the same for every
+    // `Sync` of the block
+    let (eth_price, eth_price_source) = fetch_eth_price(
+        chainlink_answers_store,
+        weth_price_store,
+        blk.timestamp().seconds,
+        heartbeat_seconds,
+    );
+    // Tokens priced so far in the block. A reference price does not change within the block, and
+    // only stands in for a token that was not priced from a pool before it.
+    let mut priced_tokens = HashSet::new();
+    let mut prices = Vec::new();
 
-                        let token0_address = pair.token0_ref().address.as_str();
-                        let token1_address = pair.token1_ref().address.as_str();
+    for log in blk.receipts().flat_map(|view| view.receipt.logs.iter()) {
+        if !abi::pair::events::Sync::match_log(log) {
+            continue;
+        }
+        // Get Sync events that have a related Pair in the store
+        let tracked = match context.pair(&log.address) {
+            Some(tracked) => tracked,
+            None => continue,
+        };
+        let event = match abi::pair::events::Sync::decode(log) {
+            Ok(event) => event,
+            Err(_) => continue,
+        };
 
-                        if STABLE_COINS.contains(&token0_address) {
-                            let token_price = reserve0.clone() / reserve1.clone();
-                            prices.push(Erc20Price {
-                                token: pair.token1.clone(),
-                                price_usd: token_price.to_string(),
-                                block_number: blk.number,
-                                ordinal: log.ordinal,
-                                source: Source::Uniswap as i32,
-                            });
-                        }
-                        if STABLE_COINS.contains(&token1_address) {
-                            let token_price = reserve1.clone() / reserve0.clone();
-                            prices.push(Erc20Price {
-                                token: pair.token0.clone(),
-                                price_usd: token_price.to_string(),
-                                block_number: blk.number,
-                                ordinal: log.ordinal,
-                                source: Source::Uniswap as i32,
-                            });
-                        }
-                        if WETH_ADDRESS.eq(token0_address) && &eth_price != &BigDecimal::zero() {
-                            let token_price =
-                                (reserve0.clone() / reserve1.clone()) * eth_price.clone();
-                            prices.push(Erc20Price {
-                                token: pair.token1.clone(),
-                                price_usd: token_price.to_string(),
-                                block_number: blk.number,
-                                ordinal: log.ordinal,
-                                source: Source::Uniswap as i32,
-                            });
-                        }
-                        if WETH_ADDRESS.eq(token1_address) && &eth_price != &BigDecimal::zero() {
-                            let token_price =
-                                (reserve1.clone() / reserve0.clone()) * eth_price.clone();
-                            prices.push(Erc20Price {
-                                token: pair.token0.clone(),
-                                price_usd: token_price.to_string(),
-                                block_number: blk.number,
-                                ordinal: log.ordinal,
-                                source: Source::Uniswap as i32,
-                            });
-                        }
-                        return Some(prices);
-                    }
-                }
-                None
-            })
-        })
-        .flatten()
-        .collect();
+        let priced = price_pair_with_anchors(PricingInput {
+            token0: tracked.token0,
+            token1: tracked.token1,
+            reserve0: event.reserve0,
+            reserve1: event.reserve1,
+            decimals0: tracked.pair.token0_ref().decimals,
+            decimals1: tracked.pair.token1_ref().decimals,
+            base_price0: tracked.base_price0.clone(),
+            base_price1: tracked.base_price1.clone(),
+            eth_price: eth_price.clone(),
+        });
 
-    Ok(Erc20Prices { items: prices })
+        let priced = priced.into_iter().filter(|price| {
+            priced_tokens.insert(price.side.select(tracked.token0, tracked.token1))
+                || price.anchor != Anchor::Reference
+        });
+
+        prices.extend(priced.map(|price| {
+            let is_reference = price.anchor == Anchor::Reference;
+            Erc20Price {
+                token: price
+                    .side
+                    .select(&tracked.pair.token0, &tracked.pair.token1)
+                    .clone(),
+                price_usd: price
+                    .price_usd
+                    .map_or_else(String::new, |price_usd| price_usd.to_string()),
+                price_eth: price
+                    .price_eth
+                    .map_or_else(String::new, |price_eth| price_eth.to_string()),
+                block_number: blk.number,
+                ordinal: log.ordinal,
+                source: if is_reference {
+                    Source::Reference as i32
+                } else {
+                    Source::Uniswap as i32
+                },
+                eth_price_source: eth_price_source as i32,
+                block_time: Some(blk.timestamp().to_owned()),
+                // A reference price does not come from the pair it was emitted with
+                pair_address: if is_reference {
+                    String::new()
+                } else {
+                    tracked.pair.pair_address.clone()
+                },
+            }
+        }));
+    }
+
+    prices
 }
 
-fn fetch_eth_price(
-    chainlink_prices_store: &StoreGetBigDecimal,
-    weth_price_store: &StoreGetProto<Erc20Price>,
-) -> BigDecimal {
-    // Attempt to get the current ETH price in USD from the imported Chainlink Prices substream store module.
-    // There may not be data as early as we need for the ETH/USD price in this store, in which case
-    // we attempt to get it from the WETH price store.
-    if let Some(eth_price) = chainlink_prices_store.get_last(StoreKey::chainlink_eth_price()) {
-        eth_price
-    } else if let Some(weth_price) = weth_price_store.get_last(StoreKey::eth_usd_price_key()) {
-        BigDecimal::from_str(weth_price.price_usd.as_str()).unwrap_or_else(|_| BigDecimal::zero())
-    } else {
-        BigDecimal::zero()
+fn fetch_eth_price<A, W>(
+    chainlink_answers_store: &A,
+    weth_price_store: &W,
+    block_seconds: i64,
+    heartbeat_seconds: i64,
+) -> (Option<BigDecimal>, Source)
+where
+    A: StoreGet<Price>,
+    W: StoreGet<Erc20Price>,
+{
+    // Attempt to get the current ETH price in USD from the latest Chainlink answer.
+    // There may not be data as early as we need for the ETH/USD price, and an answer older than the
+    // feed's heartbeat means the feed has stopped updating. In both cases we attempt to get it from
+    // the WETH price store.
+    if let Some(eth_price) = fresh_usd_answer(
+        chainlink_answers_store,
+        "ETH",
+        block_seconds,
+        heartbeat_seconds,
+    ) {
+        return (Some(eth_price), Source::Chainlink);
+    }
+
+    match weth_price_store
+        .get_last(StoreKey::eth_usd_price_key())
+        .and_then(|weth_price| BigDecimal::from_str(weth_price.price_usd.as_str()).ok())
+    {
+        Some(eth_price) => (Some(eth_price), Source::Uniswap),
+        None => (None, Source::Unknown),
     }
 }
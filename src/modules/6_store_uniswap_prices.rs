@@ -1,22 +1,43 @@
-use substreams::store::{StoreNew, StoreSet, StoreSetProto};
+use substreams::store::{StoreGetProto, StoreNew, StoreSet, StoreSetProto};
 
 use crate::{
-    pb::uniswap_pricing::v1::{Erc20Price, Erc20Prices},
+    canonical_symbols::is_canonical_symbol,
+    chainlink_feeds::superseded_by_chainlink,
+    pb::uniswap_pricing::v1::{ChainlinkPrices, Erc20Price, Erc20Prices, Erc20Token},
     store_key_manager::StoreKey,
 };
 
+// Under `pricing_mode=chainlink_first`, as resolved by `map_chainlink_prices`, Uniswap prices of
+// tokens with a fresh Chainlink USD answer are not stored.
 #[substreams::handlers::store]
-fn store_uniswap_prices(prices: Erc20Prices, output: StoreSetProto<Erc20Price>) {
-    for price in prices.items {
+fn store_uniswap_prices(
+    prices: Erc20Prices,
+    chainlink_prices: ChainlinkPrices,
+    canonical_symbols_store: StoreGetProto<Erc20Token>,
+    output: StoreSetProto<Erc20Price>,
+) {
+    for price in prices.items.iter().chain(chainlink_prices.items.iter()) {
+        // Prices against WETH from before any ETH price is known only have `price_eth`
+        if price.price_usd.is_empty() {
+            continue;
+        }
+        // Chainlink takes precedence over Uniswap for tokens with a USD feed
+        if superseded_by_chainlink(&chainlink_prices, price) {
+            continue;
+        }
+
         output.set(
             price.block_number,
             StoreKey::usd_price_by_address(&price.token_ref().address),
-            &price,
-        );
-        output.set(
-            price.block_number,
-            StoreKey::usd_price_by_symbol(&price.token_ref().symbol),
-            &price,
+            price,
         );
+        // Only the canonical token for a symbol may write the symbol keyed price
+        if is_canonical_symbol(price.token_ref(), &canonical_symbols_store) {
+            output.set(
+                price.block_number,
+                StoreKey::usd_price_by_symbol(&price.token_ref().symbol),
+                price,
+            );
+        }
     }
 }
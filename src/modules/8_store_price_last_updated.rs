@@ -0,0 +1,29 @@
+use substreams::store::{StoreMax, StoreMaxInt64, StoreNew};
+
+use crate::{
+    pb::uniswap_pricing::v1::{ChainlinkPrices, Erc20Prices},
+    store_key_manager::StoreKey,
+};
+
+// Tracks the block timestamp (unix seconds) of the latest price update per token, so consumers
+// can check how old a price is with a single lookup.
+#[substreams::handlers::store]
+fn store_price_last_updated(
+    prices: Erc20Prices,
+    chainlink_prices: ChainlinkPrices,
+    output: StoreMaxInt64,
+) {
+    for price in prices.items.into_iter().chain(chainlink_prices.items) {
+        // Not kept by `store_uniswap_prices` either
+        if price.price_usd.is_empty() {
+            continue;
+        }
+        if let Some(block_time) = &price.block_time {
+            output.max(
+                price.block_number,
+                StoreKey::last_updated_by_address(&price.token_ref().address),
+                block_time.seconds,
+            );
+        }
+    }
+}
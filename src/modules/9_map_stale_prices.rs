@@ -0,0 +1,80 @@
+use substreams::{
+    pb::substreams::Clock,
+    store::{StoreGet, StoreGetInt64, StoreGetProto},
+};
+
+use crate::{
+    params::Params,
+    pb::uniswap_pricing::v1::{Erc20Price, StalePrice, StalePrices},
+    store_key_manager::StoreKey,
+};
+
+// Default maximum age before a price is considered stale: one day.
+const DEFAULT_MAX_AGE_SECONDS: i64 = 86400;
+
+// Reports the watched tokens whose latest price is older than `max_age_seconds`, and the ones that
+// were never priced at all.
+// Params: `max_age_seconds=<seconds>&tokens=<address>,<address>,...`
+#[substreams::handlers::map]
+fn map_stale_prices(
+    params: String,
+    clock: Clock,
+    last_updated_store: StoreGetInt64,
+    prices_store: StoreGetProto<Erc20Price>,
+) -> Result<StalePrices, substreams::errors::Error> {
+    let params = Params::parse(&params);
+    let max_age_seconds = params.get_or("max_age_seconds", DEFAULT_MAX_AGE_SECONDS)?;
+    let block_seconds = clock.timestamp.map(|ts| ts.seconds).unwrap_or_default();
+
+    Ok(StalePrices {
+        items: stale_prices(
+            max_age_seconds,
+            &params.get_list("tokens"),
+            block_seconds,
+            &last_updated_store,
+            &prices_store,
+        ),
+    })
+}
+
+pub(crate) fn stale_prices<L, P>(
+    max_age_seconds: i64,
+    tokens: &[&str],
+    block_seconds: i64,
+    last_updated_store: &L,
+    prices_store: &P,
+) -> Vec<StalePrice>
+where
+    L: StoreGet<i64>,
+    P: StoreGet<Erc20Price>,
+{
+    tokens
+        .iter()
+        .filter_map(|token_address| {
+            let token_address = token_address.trim_start_matches("0x").to_lowercase();
+            let last_updated = match last_updated_store
+                .get_last(StoreKey::last_updated_by_address(&token_address))
+            {
+                Some(last_updated) => last_updated,
+                None => {
+                    return Some(StalePrice {
+                        last_price: None,
+                        age_seconds: 0,
+                        token_address,
+                    })
+                }
+            };
+            let age_seconds = block_seconds - last_updated;
+
+            if age_seconds <= max_age_seconds {
+                return None;
+            }
+
+            Some(StalePrice {
+                last_price: prices_store.get_last(StoreKey::usd_price_by_address(&token_address)),
+                age_seconds: age_seconds as u64,
+                token_address,
+            })
+        })
+        .collect()
+}
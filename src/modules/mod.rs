@@ -19,10 +19,149 @@ mod store_uniswap_prices;
 #[path = "7_warmup.rs"]
 mod warmup;
 
+#[path = "8_store_price_last_updated.rs"]
+mod store_price_last_updated;
+
+#[path = "9_map_stale_prices.rs"]
+mod map_stale_prices;
+
+#[path = "10_map_quote_prices.rs"]
+mod map_quote_prices;
+
+#[path = "11_store_quote_prices.rs"]
+mod store_quote_prices;
+
+#[path = "12_map_token_liquidity.rs"]
+mod map_token_liquidity;
+
+#[path = "13_store_pair_liquidity.rs"]
+mod store_pair_liquidity;
+
+#[path = "14_store_canonical_symbols.rs"]
+mod store_canonical_symbols;
+
+#[path = "15_store_tokens.rs"]
+mod store_tokens;
+
+#[path = "16_map_chainlink_prices.rs"]
+mod map_chainlink_prices;
+
+#[path = "17_store_chainlink_answers.rs"]
+mod store_chainlink_answers;
+
+#[path = "18_map_price_deviations.rs"]
+mod map_price_deviations;
+
+#[path = "19_db_out.rs"]
+mod db_out;
+
+#[path = "20_graph_out.rs"]
+mod graph_out;
+
+#[path = "21_ch_out.rs"]
+mod ch_out;
+
+#[path = "22_kv_out.rs"]
+mod kv_out;
+
+#[path = "23_store_historical_prices.rs"]
+mod store_historical_prices;
+
+#[path = "24_map_price_changes.rs"]
+mod map_price_changes;
+
+#[path = "25_index_uniswap_events.rs"]
+mod index_uniswap_events;
+
+#[path = "26_map_pair_depth.rs"]
+mod map_pair_depth;
+
+#[path = "27_store_pair_depth.rs"]
+mod store_pair_depth;
+
+#[path = "28_store_token_depth.rs"]
+mod store_token_depth;
+
+#[path = "29_map_pair_reserves.rs"]
+mod map_pair_reserves;
+
+#[path = "30_store_pair_reserves.rs"]
+mod store_pair_reserves;
+
+#[path = "31_store_first_pairs.rs"]
+mod store_first_pairs;
+
+#[path = "32_map_token_launches.rs"]
+mod map_token_launches;
+
+#[path = "33_map_liquidity_drains.rs"]
+mod map_liquidity_drains;
+
+#[path = "34_store_liquidity_windows.rs"]
+mod store_liquidity_windows;
+
+#[path = "35_store_flagged_tokens.rs"]
+mod store_flagged_tokens;
+
+#[path = "36_store_symbol_pairs.rs"]
+mod store_symbol_pairs;
+
+#[path = "37_map_symbol_leaders.rs"]
+mod map_symbol_leaders;
+
+#[path = "38_store_symbol_leads.rs"]
+mod store_symbol_leads;
+
+#[path = "39_map_stable_coins.rs"]
+mod map_stable_coins;
+
+#[path = "40_map_liquidity_windows.rs"]
+mod map_liquidity_windows;
+
+#[path = "41_store_lp_supply.rs"]
+mod store_lp_supply;
+
+pub use ch_out::ch_out;
+pub use db_out::db_out;
+pub use graph_out::graph_out;
+pub use index_uniswap_events::index_uniswap_events;
+pub use kv_out::kv_out;
+pub use map_chainlink_prices::map_chainlink_prices;
+pub use map_liquidity_drains::map_liquidity_drains;
+pub use map_liquidity_windows::map_liquidity_windows;
 pub use map_pair_created_events::map_pair_created_events;
+pub use map_pair_depth::map_pair_depth;
+pub use map_pair_reserves::map_pair_reserves;
+pub use map_price_changes::map_price_changes;
+pub use map_price_deviations::map_price_deviations;
+pub use map_quote_prices::map_quote_prices;
+pub use map_stable_coins::map_stable_coins;
+pub use map_stale_prices::map_stale_prices;
+pub use map_symbol_leaders::map_symbol_leaders;
+pub use map_token_launches::map_token_launches;
+pub use map_token_liquidity::map_token_liquidity;
 pub use map_uniswap_prices::map_uniswap_prices;
 pub use map_weth_prices::map_weth_prices;
+pub use store_canonical_symbols::store_canonical_symbols;
+pub use store_chainlink_answers::store_chainlink_answers;
+pub use store_first_pairs::store_first_pairs;
+pub use store_flagged_tokens::store_flagged_tokens;
+pub use store_historical_prices::store_historical_prices;
+pub use store_liquidity_windows::store_liquidity_windows;
+pub use store_lp_supply::store_lp_supply;
 pub use store_pair_created_events::store_pair_created_events;
+pub use store_pair_depth::store_pair_depth;
+pub use store_pair_liquidity::store_pair_liquidity;
+pub use store_pair_reserves::store_pair_reserves;
+pub use store_price_last_updated::store_price_last_updated;
+pub use store_quote_prices::store_quote_prices;
+pub use store_symbol_leads::store_symbol_leads;
+pub use store_symbol_pairs::store_symbol_pairs;
+pub use store_token_depth::store_token_depth;
+pub use store_tokens::store_tokens;
 pub use store_uniswap_prices::store_uniswap_prices;
 pub use store_weth_prices::store_weth_prices;
 pub use warmup::warmup;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,239 @@
+use std::{
+    collections::HashSet,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use substreams::{scalar::BigDecimal, store::StoreGet, Hex};
+use substreams_ethereum::pb::eth::v2 as eth;
+
+use super::{
+    blocks::{units, BlockBuilder},
+    pipeline::{Pipeline, ETH_USD_HEARTBEAT_SECONDS},
+    tokens::*,
+};
+use crate::{
+    abi,
+    block_context::decode_address,
+    chainlink_feeds::fresh_usd_answer,
+    modules::map_uniswap_prices::uniswap_prices,
+    pb::uniswap_pricing::v1::Erc20Token,
+    pricing::{price_pair_with_anchors, Anchor, PricingInput},
+    store_key_manager::StoreKey,
+    types::stable_coin::StablePrices,
+};
+
+const BLOCK_NUMBER: u64 = 10008355;
+const BLOCK_TIMESTAMP: i64 = 1588710145;
+
+fn synthetic_token(i: usize) -> Erc20Token {
+    erc20(&format!("{:040x}", 0x1000 + i), &format!("TKN{}", i), 18)
+}
+
+fn synthetic_pair(i: usize) -> String {
+    format!("{:040x}", 0x2000 + i)
+}
+
+// Pipeline holding an ETH price from the USDC/WETH pair, and `pairs` synthetic token/WETH pairs
+fn pipeline_with_pairs(pairs: usize) -> Pipeline {
+    let mut tokens = mainnet_tokens();
+    tokens.extend((0..pairs).map(synthetic_token));
+    let pipeline = Pipeline::new(&tokens);
+
+    let mut block = BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+        .pair_created(USDC, WETH, USDC_WETH)
+        .sync(USDC_WETH, units(2_000_000, 6), units(1_000, 18));
+    for i in 0..pairs {
+        block = block.pair_created(&synthetic_token(i).address, WETH, &synthetic_pair(i));
+    }
+    pipeline.process(&block.build());
+
+    pipeline
+}
+
+fn sync_reserves(round: usize) -> (u128, u128) {
+    (units(1_000 + round as u128, 18), units(5, 18))
+}
+
+// A block with `syncs_per_pair` interleaved `Sync` logs for each of the synthetic pairs
+fn heavy_block(pairs: usize, syncs_per_pair: usize) -> eth::Block {
+    let mut block = BlockBuilder::new(BLOCK_NUMBER + 1, BLOCK_TIMESTAMP + 13);
+    for round in 0..syncs_per_pair {
+        for i in 0..pairs {
+            let (reserve0, reserve1) = sync_reserves(round);
+            block = block.sync(&synthetic_pair(i), reserve0, reserve1);
+        }
+    }
+    block.build()
+}
+
+fn store_reads(pipeline: &Pipeline) -> usize {
+    pipeline.pairs_store.reads()
+        + pipeline.weth_price_store.reads()
+        + pipeline.chainlink_answers_store.reads()
+}
+
+// The path `map_uniswap_prices` took before `BlockContext`: every `Sync` log reads and decodes its
+// pair and resolves the ETH price again. Returns the number of prices, reference prices emitted
+// once per token and block as `map_uniswap_prices` does.
+fn per_log_prices(pipeline: &Pipeline, stable_prices: &StablePrices, blk: &eth::Block) -> usize {
+    let mut priced_tokens = HashSet::new();
+    let mut prices = 0;
+    for log in blk.receipts().flat_map(|view| view.receipt.logs.iter()) {
+        let event = match abi::pair::events::Sync::match_and_decode(log) {
+            Some(event) => event,
+            None => continue,
+        };
+        let pair = match pipeline
+            .pairs_store
+            .get_last(StoreKey::pair_key(&Hex::encode(&log.address)))
+        {
+            Some(pair) => pair,
+            None => continue,
+        };
+        let eth_price = fresh_usd_answer(
+            &pipeline.chainlink_answers_store,
+            "ETH",
+            blk.timestamp().seconds,
+            ETH_USD_HEARTBEAT_SECONDS,
+        )
+        .or_else(|| {
+            pipeline
+                .weth_price_store
+                .get_last(StoreKey::eth_usd_price_key())
+                .and_then(|price| BigDecimal::from_str(&price.price_usd).ok())
+        });
+
+        let (token0, token1) = (pair.token0_ref(), pair.token1_ref());
+        let (address0, address1) = (
+            decode_address(&token0.address).unwrap(),
+            decode_address(&token1.address).unwrap(),
+        );
+        prices += price_pair_with_anchors(PricingInput {
+            token0: address0,
+            token1: address1,
+            reserve0: event.reserve0,
+            reserve1: event.reserve1,
+            decimals0: token0.decimals,
+            decimals1: token1.decimals,
+            base_price0: stable_prices.usd_price(&token0.address),
+            base_price1: stable_prices.usd_price(&token1.address),
+            eth_price,
+        })
+        .into_iter()
+        .filter(|price| {
+            priced_tokens.insert(price.side.select(address0, address1))
+                || price.anchor != Anchor::Reference
+        })
+        .count();
+    }
+    prices
+}
+
+#[test]
+fn reads_the_stores_once_per_block_and_pair() {
+    let pipeline = pipeline_with_pairs(20);
+    let blk = heavy_block(20, 5);
+    let chainlink_answer_reads = pipeline.chainlink_answers_store.reads();
+    pipeline.stable_prices(&blk);
+    let stable_coin_reads = pipeline.chainlink_answers_store.reads() - chainlink_answer_reads;
+
+    let pair_reads = pipeline.pairs_store.reads();
+    let weth_price_reads = pipeline.weth_price_store.reads();
+    let chainlink_answer_reads = pipeline.chainlink_answers_store.reads();
+    let output = pipeline.process(&blk);
+    // A price per `Sync`, and one for WETH
+    assert_eq!(output.uniswap_prices.items.len(), 100 + 1);
+
+    // `map_weth_prices` and `map_uniswap_prices` each read every pair once
+    assert_eq!(pipeline.pairs_store.reads() - pair_reads, 2 * 20);
+    // The ETH price is resolved once for the whole block, on top of the stablecoin prices
+    assert_eq!(pipeline.weth_price_store.reads() - weth_price_reads, 1);
+    assert_eq!(
+        pipeline.chainlink_answers_store.reads() - chainlink_answer_reads,
+        stable_coin_reads + 1
+    );
+}
+
+#[test]
+fn resolves_stablecoin_prices_once_per_block() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    pipeline.process(&BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP).build());
+    let empty_block_reads = pipeline.chainlink_answers_store.reads();
+
+    pipeline.process(
+        &BlockBuilder::new(BLOCK_NUMBER + 1, BLOCK_TIMESTAMP + 13)
+            .pair_created(USDC, WETH, USDC_WETH)
+            .sync(USDC_WETH, units(2_000_000, 6), units(1_000, 18))
+            .sync(USDC_WETH, units(2_000_100, 6), units(1_000, 18))
+            .sync(USDC_WETH, units(2_000_200, 6), units(1_000, 18))
+            .build(),
+    );
+
+    // `map_stable_coins` reads the feeds whatever the block holds, pricing reads none of them
+    assert_eq!(
+        pipeline.chainlink_answers_store.reads(),
+        2 * empty_block_reads
+    );
+}
+
+// Prices a block with many `Sync` logs per pair through the per-log path and through
+// `map_uniswap_prices`, counting store reads and timing each. Run with
+// `cargo test --release -- --nocapture benchmarks_a_heavy_block` to see the timings.
+#[test]
+fn benchmarks_a_heavy_block_against_the_per_log_path() {
+    const PAIRS: usize = 200;
+    const SYNCS_PER_PAIR: usize = 5;
+    const RUNS: u32 = 5;
+
+    let pipeline = pipeline_with_pairs(PAIRS);
+    let blk = heavy_block(PAIRS, SYNCS_PER_PAIR);
+    let stable_prices = pipeline.stable_prices(&blk);
+
+    let run = |price: &dyn Fn() -> usize| -> (usize, usize, Duration) {
+        let reads = store_reads(&pipeline);
+        let start = Instant::now();
+        let mut prices = 0;
+        for _ in 0..RUNS {
+            prices = price();
+        }
+        (
+            prices,
+            (store_reads(&pipeline) - reads) / RUNS as usize,
+            start.elapsed() / RUNS,
+        )
+    };
+    let (per_log_prices, per_log_reads, per_log_time) =
+        run(&|| per_log_prices(&pipeline, &stable_prices, &blk));
+    let (context_prices, context_reads, context_time) = run(&|| {
+        uniswap_prices(
+            &stable_prices,
+            ETH_USD_HEARTBEAT_SECONDS,
+            &blk,
+            &pipeline.pairs_store,
+            &pipeline.weth_price_store,
+            &pipeline.chainlink_answers_store,
+        )
+        .len()
+    });
+
+    println!(
+        "{} Sync logs, per log: {} store reads in {:?}, block context: {} store reads in {:?}",
+        PAIRS * SYNCS_PER_PAIR,
+        per_log_reads,
+        per_log_time,
+        context_reads,
+        context_time
+    );
+    println!(
+        "block context: {:.1}x fewer store reads, {:.1}x faster",
+        per_log_reads as f64 / context_reads as f64,
+        per_log_time.as_secs_f64() / context_time.as_secs_f64()
+    );
+
+    assert_eq!(per_log_prices, context_prices);
+    // Per log: the pair, the Chainlink ETH answer and, as it is not seeded, the WETH price
+    assert_eq!(per_log_reads, 3 * PAIRS * SYNCS_PER_PAIR);
+    // Block context: each pair once, and the ETH price once for the block
+    assert_eq!(context_reads, PAIRS + 2);
+}
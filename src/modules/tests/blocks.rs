@@ -0,0 +1,281 @@
+use ethabi::{Address, Token, Uint};
+use hex_literal::hex;
+use prost_types::Timestamp;
+use substreams_ethereum::pb::eth::v2 as eth;
+
+use crate::modules::map_pair_created_events::TRACKED_CONTRACT;
+
+const PAIR_CREATED_TOPIC: [u8; 32] =
+    hex!("0d3648bd0f6ba80134a33ba9275ac585d9d315f0ad8355cddefde31afa28d0e9");
+const SYNC_TOPIC: [u8; 32] =
+    hex!("1c411e9a96e071241c2f21f7726b17ae89e3cab4c78be50e062b03a9fffbbad1");
+const MINT_TOPIC: [u8; 32] =
+    hex!("4c209b5fc8ad50758f13e2e1088ba56a560dff690a1c6fef26394f4c03821c4f");
+const BURN_TOPIC: [u8; 32] =
+    hex!("dccd412f0b1252819cb1fd330b93224ca42612892bb3f4f789976e6d81936496");
+const TRANSFER_TOPIC: [u8; 32] =
+    hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+const ZERO_ADDRESS: &str = "0000000000000000000000000000000000000000";
+
+// Sender of every synthetic transaction
+pub const TX_FROM: &str = "00000000000000000000000000000000000000ee";
+// `sender` of `Mint` and `Burn` logs, the router on mainnet
+pub const ROUTER: &str = "7a250d5630b4cf539739df2c5dacb4c659f2488d";
+
+// Builds synthetic blocks holding Uniswap V2 factory and pair logs. Every log is emitted by its
+// own successful transaction, in the order it is added, except for first mints, liquidity
+// removals and swaps.
+pub struct BlockBuilder {
+    block: eth::Block,
+    ordinal: u64,
+}
+
+impl BlockBuilder {
+    pub fn new(number: u64, timestamp_seconds: i64) -> Self {
+        BlockBuilder {
+            block: eth::Block {
+                number,
+                header: Some(eth::BlockHeader {
+                    number,
+                    timestamp: Some(Timestamp {
+                        seconds: timestamp_seconds,
+                        nanos: 0,
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ordinal: 0,
+        }
+    }
+
+    // Factory `PairCreated(token0, token1, pair, allPairsLength)` log
+    pub fn pair_created(self, token0: &str, token1: &str, pair: &str) -> Self {
+        let data = ethabi::encode(&[
+            Token::Address(Address::from_slice(&decode_hex(pair))),
+            Token::Uint(Uint::from(1)),
+        ]);
+        self.log(
+            TRACKED_CONTRACT.to_vec(),
+            vec![
+                PAIR_CREATED_TOPIC.to_vec(),
+                address_topic(token0),
+                address_topic(token1),
+            ],
+            data,
+        )
+    }
+
+    // Pair `Sync(reserve0, reserve1)` log, reserves are in each token's smallest unit
+    pub fn sync(self, pair: &str, reserve0: u128, reserve1: u128) -> Self {
+        let data = ethabi::encode(&[
+            Token::Uint(Uint::from(reserve0)),
+            Token::Uint(Uint::from(reserve1)),
+        ]);
+        self.log(decode_hex(pair), vec![SYNC_TOPIC.to_vec()], data)
+    }
+
+    // Pair `Mint(sender, amount0, amount1)` log, amounts are in each token's smallest unit
+    pub fn mint(self, pair: &str, amount0: u128, amount1: u128) -> Self {
+        let data = ethabi::encode(&[
+            Token::Uint(Uint::from(amount0)),
+            Token::Uint(Uint::from(amount1)),
+        ]);
+        self.log(
+            decode_hex(pair),
+            vec![MINT_TOPIC.to_vec(), address_topic(ROUTER)],
+            data,
+        )
+    }
+
+    // Router `addLiquidity` transaction funding a pair with no LP supply: the pair locks the
+    // minimum liquidity at the zero address, mints the rest to the sender, syncs to `reserve0`
+    // and `reserve1` and logs the `Mint` of `amount0` and `amount1`. Reserves exceed the amounts
+    // when tokens were sent to the pair beforehand.
+    pub fn first_mint(
+        self,
+        pair: &str,
+        amount0: u128,
+        amount1: u128,
+        reserve0: u128,
+        reserve1: u128,
+    ) -> Self {
+        let minimum_liquidity = Uint::from(1000);
+        let liquidity =
+            (Uint::from(amount0) * Uint::from(amount1)).integer_sqrt() - minimum_liquidity;
+        let transfer = |to: &str, amount: Uint| {
+            (
+                decode_hex(pair),
+                vec![
+                    TRANSFER_TOPIC.to_vec(),
+                    address_topic(ZERO_ADDRESS),
+                    address_topic(to),
+                ],
+                ethabi::encode(&[Token::Uint(amount)]),
+            )
+        };
+        let sync = ethabi::encode(&[
+            Token::Uint(Uint::from(reserve0)),
+            Token::Uint(Uint::from(reserve1)),
+        ]);
+        let mint = ethabi::encode(&[
+            Token::Uint(Uint::from(amount0)),
+            Token::Uint(Uint::from(amount1)),
+        ]);
+        self.transaction(vec![
+            transfer(ZERO_ADDRESS, minimum_liquidity),
+            transfer(TX_FROM, liquidity),
+            (decode_hex(pair), vec![SYNC_TOPIC.to_vec()], sync),
+            (
+                decode_hex(pair),
+                vec![MINT_TOPIC.to_vec(), address_topic(ROUTER)],
+                mint,
+            ),
+        ])
+    }
+
+    // Router `removeLiquidityETH` transaction: `holder` sends `liquidity` LP tokens to the pair,
+    // which burns them to the zero address and pays `amount0` and `amount1` out to the router. The
+    // matching `Sync` is added separately.
+    pub fn remove_liquidity(
+        self,
+        pair: &str,
+        holder: &str,
+        liquidity: u128,
+        amount0: u128,
+        amount1: u128,
+    ) -> Self {
+        let transfer = ethabi::encode(&[Token::Uint(Uint::from(liquidity))]);
+        let burn = ethabi::encode(&[
+            Token::Uint(Uint::from(amount0)),
+            Token::Uint(Uint::from(amount1)),
+        ]);
+        self.transaction(vec![
+            (
+                decode_hex(pair),
+                vec![
+                    TRANSFER_TOPIC.to_vec(),
+                    address_topic(holder),
+                    address_topic(pair),
+                ],
+                transfer.clone(),
+            ),
+            (
+                decode_hex(pair),
+                vec![
+                    TRANSFER_TOPIC.to_vec(),
+                    address_topic(pair),
+                    address_topic(ZERO_ADDRESS),
+                ],
+                transfer,
+            ),
+            (
+                decode_hex(pair),
+                vec![
+                    BURN_TOPIC.to_vec(),
+                    address_topic(ROUTER),
+                    address_topic(ROUTER),
+                ],
+                burn,
+            ),
+        ])
+    }
+
+    // Swap transaction: `amount_in` of `token_in` is sent to the pair, which pays `amount_out` of
+    // `token_out` out and syncs to the new reserves. The `Swap` log itself is left out.
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap(
+        self,
+        pair: &str,
+        token_in: &str,
+        token_out: &str,
+        amount_in: u128,
+        amount_out: u128,
+        reserve0: u128,
+        reserve1: u128,
+    ) -> Self {
+        let transfer = |amount: u128| ethabi::encode(&[Token::Uint(Uint::from(amount))]);
+        let sync = ethabi::encode(&[
+            Token::Uint(Uint::from(reserve0)),
+            Token::Uint(Uint::from(reserve1)),
+        ]);
+        self.transaction(vec![
+            (
+                decode_hex(token_in),
+                vec![
+                    TRANSFER_TOPIC.to_vec(),
+                    address_topic(TX_FROM),
+                    address_topic(pair),
+                ],
+                transfer(amount_in),
+            ),
+            (
+                decode_hex(token_out),
+                vec![
+                    TRANSFER_TOPIC.to_vec(),
+                    address_topic(pair),
+                    address_topic(TX_FROM),
+                ],
+                transfer(amount_out),
+            ),
+            (decode_hex(pair), vec![SYNC_TOPIC.to_vec()], sync),
+        ])
+    }
+
+    pub fn build(self) -> eth::Block {
+        self.block
+    }
+
+    fn log(self, address: Vec<u8>, topics: Vec<Vec<u8>>, data: Vec<u8>) -> Self {
+        self.transaction(vec![(address, topics, data)])
+    }
+
+    fn transaction(mut self, logs: Vec<(Vec<u8>, Vec<Vec<u8>>, Vec<u8>)>) -> Self {
+        let index = self.block.transaction_traces.len() as u32;
+        let mut receipt_logs = Vec::new();
+
+        for (log_index, (address, topics, data)) in logs.into_iter().enumerate() {
+            self.ordinal += 1;
+            receipt_logs.push(eth::Log {
+                address,
+                topics,
+                data,
+                index: log_index as u32,
+                block_index: (self.ordinal - 1) as u32,
+                ordinal: self.ordinal,
+            });
+        }
+
+        self.block.transaction_traces.push(eth::TransactionTrace {
+            hash: vec![index as u8; 32],
+            from: decode_hex(TX_FROM),
+            index,
+            status: eth::TransactionTraceStatus::Succeeded as i32,
+            receipt: Some(eth::TransactionReceipt {
+                logs: receipt_logs,
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        self
+    }
+}
+
+// Amount of a token in its smallest unit, e.g. `units(2_000, 6)` for 2,000 USDC
+pub fn units(amount: u128, decimals: u32) -> u128 {
+    amount * 10u128.pow(decimals)
+}
+
+fn address_topic(address: &str) -> Vec<u8> {
+    let mut topic = vec![0u8; 12];
+    topic.extend(decode_hex(address));
+    topic
+}
+
+fn decode_hex(value: &str) -> Vec<u8> {
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).expect("invalid hex"))
+        .collect()
+}
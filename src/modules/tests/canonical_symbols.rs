@@ -0,0 +1,191 @@
+use substreams::{pb::substreams::store_delta::Operation, scalar::BigDecimal, store::DeltaProto};
+
+use super::{
+    blocks::{units, BlockBuilder},
+    memory_store::MemoryStore,
+    pipeline::Chain,
+    tokens::*,
+};
+use crate::{
+    modules::{
+        map_symbol_leaders::symbol_leaders, map_token_liquidity::token_liquidities,
+        store_canonical_symbols::set_canonical_symbols, store_pair_liquidity::set_pair_liquidities,
+        store_symbol_leads::set_symbol_leads, store_symbol_pairs::liquid_symbol_pairs,
+    },
+    pb::uniswap_pricing::v1::{Erc20Token, SymbolLeaders, TokenLiquidities, TokenLiquidity},
+    store_key_manager::StoreKey,
+};
+
+const BLOCK_NUMBER: u64 = 10008355;
+const BLOCK_TIMESTAMP: i64 = 1588710145;
+const TAKEOVER_BLOCKS: u64 = 10;
+
+// Two tokens sharing the `PEPE` symbol, each with a pair against WETH
+const OLD: &str = "00000000000000000000000000000000000000d1";
+const NEW: &str = "00000000000000000000000000000000000000d2";
+const OLD_WETH: &str = "00000000000000000000000000000000000000e1";
+const NEW_WETH: &str = "00000000000000000000000000000000000000e2";
+
+// `Chain` along with the modules picking the canonical token of a symbol
+struct Symbols {
+    chain: Chain,
+    pair_liquidity_store: MemoryStore<TokenLiquidity>,
+    symbol_pairs_store: MemoryStore<Vec<String>>,
+    leads_store: MemoryStore<i64>,
+    canonical_store: MemoryStore<Erc20Token>,
+}
+
+impl Symbols {
+    // OLD holds the symbol with 400,000 USD of liquidity
+    fn new() -> Self {
+        let mut tokens = mainnet_tokens();
+        tokens.push(erc20(OLD, "PEPE", 18));
+        tokens.push(erc20(NEW, "PEPE", 18));
+
+        let mut symbols = Symbols {
+            chain: Chain::new(&tokens, BLOCK_NUMBER, BLOCK_TIMESTAMP),
+            pair_liquidity_store: MemoryStore::default(),
+            symbol_pairs_store: MemoryStore::default(),
+            leads_store: MemoryStore::default(),
+            canonical_store: MemoryStore::default(),
+        };
+        // 2,000 USDC per WETH
+        symbols.process(|block| {
+            block
+                .pair_created(USDC, WETH, USDC_WETH)
+                .sync(USDC_WETH, units(2_000_000, 6), units(1_000, 18))
+                .pair_created(OLD, WETH, OLD_WETH)
+                .pair_created(NEW, WETH, NEW_WETH)
+        });
+        for _ in 0..=TAKEOVER_BLOCKS {
+            symbols.process(|block| block.sync(OLD_WETH, units(1_000_000, 18), units(100, 18)));
+        }
+        assert_eq!(symbols.canonical(), Some(OLD.to_string()));
+        symbols
+    }
+
+    fn process(&mut self, build: impl FnOnce(BlockBuilder) -> BlockBuilder) {
+        let (blk, _) = self.chain.process(build);
+        self.chain.next_block();
+
+        let pipeline = &self.chain.pipeline;
+        let liquidities = TokenLiquidities {
+            items: token_liquidities(
+                &pipeline.stable_prices(&blk),
+                &blk,
+                &pipeline.pairs_store,
+                &pipeline.weth_price_store,
+            ),
+            min_liquidity_usd: "50000".to_string(),
+            takeover_margin_percent: "10".to_string(),
+            takeover_blocks: TAKEOVER_BLOCKS,
+        };
+
+        let deltas = self.pair_liquidity_deltas(&liquidities);
+        set_pair_liquidities(liquidities.clone(), &self.pair_liquidity_store);
+        for (_, key, pair) in liquid_symbol_pairs(&BigDecimal::from(50000), deltas) {
+            let mut pairs = self.symbol_pairs_store.value(&key).unwrap_or_default();
+            pairs.push(pair);
+            self.symbol_pairs_store.insert(key, pairs);
+        }
+
+        let leaders = SymbolLeaders {
+            items: symbol_leaders(
+                &liquidities,
+                &self.pair_liquidity_store,
+                &self.symbol_pairs_store,
+            ),
+        };
+        set_symbol_leads(leaders.clone(), &self.leads_store);
+        set_canonical_symbols(leaders, &self.leads_store, &self.canonical_store);
+    }
+
+    // Deltas `store_pair_liquidity` emits for the block
+    fn pair_liquidity_deltas(
+        &self,
+        liquidities: &TokenLiquidities,
+    ) -> Vec<DeltaProto<TokenLiquidity>> {
+        liquidities
+            .items
+            .iter()
+            .map(|liquidity| {
+                let key = StoreKey::pair_liquidity(&liquidity.pair_address);
+                let old_value = self.pair_liquidity_store.value(&key);
+
+                DeltaProto {
+                    operation: if old_value.is_some() {
+                        Operation::Update
+                    } else {
+                        Operation::Create
+                    },
+                    ordinal: liquidity.ordinal,
+                    key,
+                    old_value: old_value.unwrap_or_default(),
+                    new_value: liquidity.clone(),
+                }
+            })
+            .collect()
+    }
+
+    fn canonical(&self) -> Option<String> {
+        self.canonical_store
+            .value(&StoreKey::canonical_token_by_symbol("PEPE"))
+            .map(|token| token.address)
+    }
+}
+
+#[test]
+fn flash_liquidity_does_not_take_a_symbol_over() {
+    let mut symbols = Symbols::new();
+
+    // 10,000 WETH added and removed within every block is never seen at the end of it
+    for _ in 0..=TAKEOVER_BLOCKS {
+        symbols.process(|block| {
+            block
+                .sync(NEW_WETH, units(100_000_000, 18), units(10_000, 18))
+                .sync(NEW_WETH, units(1_000, 18), units(1, 18))
+        });
+    }
+
+    assert_eq!(symbols.canonical(), Some(OLD.to_string()));
+}
+
+#[test]
+fn takes_a_symbol_over_after_a_sustained_lead() {
+    let mut symbols = Symbols::new();
+
+    // 200 WETH against the 100 WETH of the holder, held one block short of the takeover
+    for _ in 0..TAKEOVER_BLOCKS {
+        symbols.process(|block| block.sync(NEW_WETH, units(1_000_000, 18), units(200, 18)));
+    }
+    assert_eq!(symbols.canonical(), Some(OLD.to_string()));
+
+    symbols.process(|block| block.sync(NEW_WETH, units(1_000_000, 18), units(200, 18)));
+    assert_eq!(symbols.canonical(), Some(NEW.to_string()));
+}
+
+#[test]
+fn losing_the_lead_restarts_it() {
+    let mut symbols = Symbols::new();
+
+    for _ in 0..TAKEOVER_BLOCKS {
+        symbols.process(|block| block.sync(NEW_WETH, units(1_000_000, 18), units(200, 18)));
+    }
+    // The holder catches up for a block
+    symbols.process(|block| block.sync(OLD_WETH, units(1_000_000, 18), units(200, 18)));
+    symbols.process(|block| block.sync(OLD_WETH, units(1_000_000, 18), units(100, 18)));
+
+    assert_eq!(symbols.canonical(), Some(OLD.to_string()));
+}
+
+#[test]
+fn keeps_the_holder_without_the_takeover_margin() {
+    let mut symbols = Symbols::new();
+
+    // 105 WETH against 100 WETH is within the 10% margin
+    for _ in 0..=TAKEOVER_BLOCKS * 2 {
+        symbols.process(|block| block.sync(NEW_WETH, units(1_000_000, 18), units(105, 18)));
+    }
+
+    assert_eq!(symbols.canonical(), Some(OLD.to_string()));
+}
@@ -0,0 +1,27 @@
+use crate::modules::ch_out::to_price_decimal;
+
+#[test]
+fn pads_prices_to_the_column_scale() {
+    assert_eq!(
+        to_price_decimal("2000").as_deref(),
+        Some("2000.000000000000000000")
+    );
+    assert_eq!(
+        to_price_decimal("0.5").as_deref(),
+        Some("0.500000000000000000")
+    );
+}
+
+#[test]
+fn truncates_digits_beyond_the_column_scale() {
+    assert_eq!(
+        to_price_decimal("0.0000123456789012345678901").as_deref(),
+        Some("0.000012345678901234")
+    );
+}
+
+#[test]
+fn skips_prices_that_are_not_decimals() {
+    assert_eq!(to_price_decimal(""), None);
+    assert_eq!(to_price_decimal("1e-7"), None);
+}
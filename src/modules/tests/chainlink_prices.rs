@@ -0,0 +1,151 @@
+use super::{
+    assert_price_eq,
+    blocks::{units, BlockBuilder},
+    memory_store::MemoryStore,
+    tokens::*,
+    usd_answer,
+};
+use crate::{
+    modules::map_chainlink_prices::{chainlink_priced_tokens, chainlink_prices},
+    pb::{
+        chainlink::v1::Prices,
+        uniswap_pricing::v1::{erc20_price::Source, Erc20Token},
+    },
+    store_key_manager::StoreKey,
+};
+
+const BLOCK_NUMBER: u64 = 10008355;
+const BLOCK_TIMESTAMP: i64 = 1588710145;
+const HEARTBEAT_SECONDS: i64 = 86400;
+const ETH_USD_HEARTBEAT_SECONDS: i64 = 3600;
+
+fn tokens_store() -> MemoryStore<Erc20Token> {
+    let store = MemoryStore::default();
+    for token in mainnet_tokens() {
+        store.insert(StoreKey::token_key(&token.address), token);
+    }
+    store
+}
+
+#[test]
+fn prices_tokens_after_every_log_of_the_block() {
+    let blk = BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+        .sync(USDC_WETH, units(2_000_000, 6), units(1_000, 18))
+        .sync(USDC_GRT, units(500, 6), units(1_000, 18))
+        .build();
+    let chainlink_answers_store = MemoryStore::default();
+    chainlink_answers_store.insert(
+        StoreKey::chainlink_answer("ETH", "USD"),
+        usd_answer("ETH", "2000", BLOCK_TIMESTAMP - 60),
+    );
+
+    let prices = chainlink_prices(
+        HEARTBEAT_SECONDS,
+        ETH_USD_HEARTBEAT_SECONDS,
+        &blk,
+        &Prices {
+            items: vec![usd_answer("GRT", "0.5", BLOCK_TIMESTAMP)],
+        },
+        &tokens_store(),
+        &chainlink_answers_store,
+    );
+
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices[0].token_ref().address, GRT);
+    assert_price_eq(&prices[0].price_usd, "0.5");
+    assert_price_eq(&prices[0].price_eth, "0.00025");
+    assert_eq!(prices[0].ordinal, 2);
+    assert_eq!(prices[0].eth_price_source, Source::Chainlink as i32);
+}
+
+#[test]
+fn leaves_the_eth_price_unset_when_the_eth_answer_is_stale() {
+    let blk = BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP).build();
+    let chainlink_answers_store = MemoryStore::default();
+    chainlink_answers_store.insert(
+        StoreKey::chainlink_answer("ETH", "USD"),
+        usd_answer(
+            "ETH",
+            "2000",
+            BLOCK_TIMESTAMP - ETH_USD_HEARTBEAT_SECONDS - 1,
+        ),
+    );
+
+    let prices = chainlink_prices(
+        HEARTBEAT_SECONDS,
+        ETH_USD_HEARTBEAT_SECONDS,
+        &blk,
+        &Prices {
+            items: vec![usd_answer("GRT", "0.5", BLOCK_TIMESTAMP)],
+        },
+        &tokens_store(),
+        &chainlink_answers_store,
+    );
+
+    assert_eq!(prices.len(), 1);
+    assert_price_eq(&prices[0].price_usd, "0.5");
+    assert_eq!(prices[0].price_eth, "");
+    assert_eq!(prices[0].eth_price_source, Source::Unknown as i32);
+}
+
+#[test]
+fn ignores_answers_older_than_the_heartbeat() {
+    let blk = BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP).build();
+
+    let prices = chainlink_prices(
+        HEARTBEAT_SECONDS,
+        ETH_USD_HEARTBEAT_SECONDS,
+        &blk,
+        &Prices {
+            items: vec![usd_answer(
+                "GRT",
+                "0.5",
+                BLOCK_TIMESTAMP - HEARTBEAT_SECONDS - 1,
+            )],
+        },
+        &tokens_store(),
+        &MemoryStore::default(),
+    );
+
+    assert!(prices.is_empty());
+}
+
+#[test]
+fn holds_the_eth_answer_to_the_eth_usd_heartbeat() {
+    let blk = BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP).build();
+    // Older than the ETH/USD heartbeat, within the heartbeat of the other feeds
+    let eth_answer_timestamp = BLOCK_TIMESTAMP - 2 * ETH_USD_HEARTBEAT_SECONDS;
+    let chainlink_answers_store = MemoryStore::default();
+    chainlink_answers_store.insert(
+        StoreKey::chainlink_answer("ETH", "USD"),
+        usd_answer("ETH", "2000", eth_answer_timestamp),
+    );
+    chainlink_answers_store.insert(
+        StoreKey::chainlink_answer("GRT", "USD"),
+        usd_answer("GRT", "0.5", eth_answer_timestamp),
+    );
+
+    let prices = chainlink_prices(
+        HEARTBEAT_SECONDS,
+        ETH_USD_HEARTBEAT_SECONDS,
+        &blk,
+        &Prices {
+            items: vec![
+                usd_answer("ETH", "2000", eth_answer_timestamp),
+                usd_answer("GRT", "0.5", eth_answer_timestamp),
+            ],
+        },
+        &tokens_store(),
+        &chainlink_answers_store,
+    );
+    let priced_tokens = chainlink_priced_tokens(
+        HEARTBEAT_SECONDS,
+        ETH_USD_HEARTBEAT_SECONDS,
+        BLOCK_TIMESTAMP,
+        &chainlink_answers_store,
+    );
+
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices[0].token_ref().address, GRT);
+    assert_eq!(priced_tokens, vec![GRT.to_string()]);
+}
@@ -0,0 +1,62 @@
+use substreams_database_change::{pb::database::TableChange, tables::Tables};
+
+use super::{price_delta, tokens::*};
+use crate::{
+    modules::db_out::set_prices,
+    pb::uniswap_pricing::v1::{erc20_price::Source, Erc20Price},
+};
+
+fn price(price_usd: &str, price_eth: &str) -> Erc20Price {
+    Erc20Price {
+        token: Some(erc20(GRT, "GRT", 18)),
+        price_usd: price_usd.to_string(),
+        price_eth: price_eth.to_string(),
+        pair_address: WETH_GRT.to_string(),
+        block_number: 10008355,
+        ordinal: 2,
+        source: Source::Chainlink as i32,
+        ..Default::default()
+    }
+}
+
+fn field<'a>(change: &'a TableChange, name: &str) -> Option<&'a str> {
+    change
+        .fields
+        .iter()
+        .find(|field| field.name == name)
+        .map(|field| field.new_value.as_str())
+}
+
+#[test]
+fn writes_the_eth_price_of_every_row() {
+    let mut tables = Tables::new();
+    set_prices(
+        &mut tables,
+        vec![price_delta(None, price("0.5", "0.00025"))],
+    );
+
+    let changes = tables.to_database_changes().table_changes;
+
+    assert_eq!(changes.len(), 2);
+    for change in &changes {
+        assert_eq!(field(change, "price_usd"), Some("0.5"));
+        assert_eq!(field(change, "price_eth"), Some("0.00025"));
+    }
+}
+
+#[test]
+fn leaves_out_a_missing_eth_price() {
+    let mut tables = Tables::new();
+    set_prices(
+        &mut tables,
+        vec![price_delta(Some(price("0.4", "0.0002")), price("0.5", ""))],
+    );
+
+    let changes = tables.to_database_changes().table_changes;
+
+    assert_eq!(changes.len(), 2);
+    for change in &changes {
+        assert_eq!(field(change, "price_usd"), Some("0.5"));
+        assert_eq!(field(change, "price_eth"), None);
+    }
+}
@@ -0,0 +1,154 @@
+use substreams::{pb::substreams::store_delta::Operation, scalar::BigDecimal, store::DeltaProto};
+use substreams_ethereum::pb::eth::v2 as eth;
+
+use super::{
+    assert_price_eq,
+    blocks::{units, BlockBuilder},
+    memory_store::MemoryStore,
+    pipeline::Pipeline,
+    tokens::*,
+};
+use crate::{
+    modules::{map_pair_depth::pair_depths, store_token_depth::add_token_depths},
+    pb::uniswap_pricing::v1::{DepthLevel, PairDepth},
+    store_key_manager::StoreKey,
+};
+
+const BLOCK_NUMBER: u64 = 10008355;
+const BLOCK_TIMESTAMP: i64 = 1588710145;
+
+fn depths(pipeline: &Pipeline, blk: &eth::Block) -> Vec<PairDepth> {
+    pipeline.process(blk);
+    pair_depths(
+        &pipeline.stable_prices(blk),
+        blk,
+        &pipeline.pairs_store,
+        &pipeline.weth_price_store,
+    )
+}
+
+#[test]
+fn values_stablecoin_pairs_from_the_stablecoin_reserve() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    let depths = depths(
+        &pipeline,
+        &BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+            .pair_created(USDC, GRT, USDC_GRT)
+            .sync(USDC_GRT, units(500, 6), units(1_000, 18))
+            .build(),
+    );
+
+    assert_eq!(depths.len(), 1);
+    let depth = &depths[0];
+    assert_eq!(depth.pair_address, USDC_GRT);
+    assert_price_eq(&depth.reserve_usd, "500");
+
+    let percents: Vec<u32> = depth.levels.iter().map(|level| level.percent).collect();
+    assert_eq!(percents, vec![1, 2, 5]);
+    // 500 * (sqrt(1.02) - 1) and 500 * (1 / sqrt(0.98) - 1)
+    assert_price_eq(&depth.levels[1].up_usd, "4.9752469181039");
+    assert_price_eq(&depth.levels[1].down_usd, "5.0762722761053");
+}
+
+#[test]
+fn values_weth_pairs_at_the_eth_price() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    pipeline.process(
+        &BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+            .pair_created(USDC, WETH, USDC_WETH)
+            .sync(USDC_WETH, units(2_000_000, 6), units(1_000, 18))
+            .build(),
+    );
+
+    let depths = depths(
+        &pipeline,
+        &BlockBuilder::new(BLOCK_NUMBER + 1, BLOCK_TIMESTAMP + 13)
+            .pair_created(WETH, GRT, WETH_GRT)
+            .sync(WETH_GRT, units(10, 18), units(100_000, 18))
+            .build(),
+    );
+
+    assert_eq!(depths.len(), 1);
+    assert_eq!(depths[0].pair_address, WETH_GRT);
+    assert_price_eq(&depths[0].reserve_usd, "20000");
+}
+
+#[test]
+fn skips_pairs_without_a_known_price() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    // No ETH price is known yet
+    let depths = depths(
+        &pipeline,
+        &BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+            .pair_created(WETH, GRT, WETH_GRT)
+            .sync(WETH_GRT, units(10, 18), units(100_000, 18))
+            .build(),
+    );
+
+    assert!(depths.is_empty());
+}
+
+// Depth of a pair with a single 2% level
+fn pair_depth(pair: &str, token0: &str, token1: &str, up_usd: &str, down_usd: &str) -> PairDepth {
+    PairDepth {
+        pair_address: pair.to_string(),
+        token0: Some(erc20(token0, "", 18)),
+        token1: Some(erc20(token1, "", 18)),
+        levels: vec![DepthLevel {
+            percent: 2,
+            up_usd: up_usd.to_string(),
+            down_usd: down_usd.to_string(),
+        }],
+        ..Default::default()
+    }
+}
+
+fn depth_delta(old_value: Option<PairDepth>, new_value: PairDepth) -> DeltaProto<PairDepth> {
+    DeltaProto {
+        operation: if old_value.is_some() {
+            Operation::Update
+        } else {
+            Operation::Create
+        },
+        ordinal: 0,
+        key: StoreKey::pair_depth(&new_value.pair_address),
+        old_value: old_value.unwrap_or_default(),
+        new_value,
+    }
+}
+
+#[test]
+fn sums_the_latest_depth_of_every_pair_of_a_token() {
+    let store = MemoryStore::<BigDecimal>::default();
+    let usdc_grt = pair_depth(USDC_GRT, USDC, GRT, "10", "11");
+    let weth_grt = pair_depth(WETH_GRT, WETH, GRT, "20", "22");
+
+    add_token_depths(
+        &[
+            depth_delta(None, usdc_grt.clone()),
+            depth_delta(None, weth_grt),
+        ],
+        &store,
+    );
+    add_token_depths(
+        &[depth_delta(
+            Some(usdc_grt),
+            pair_depth(USDC_GRT, USDC, GRT, "15", "16"),
+        )],
+        &store,
+    );
+
+    let total = |token: &str, direction: &str| {
+        store
+            .value(&StoreKey::token_depth(token, 2, direction))
+            .expect("missing token depth")
+            .to_string()
+    };
+    // token0 and token1 of a pair both get its up and down levels as they are
+    assert_price_eq(&total(USDC, "up"), "15");
+    assert_price_eq(&total(USDC, "down"), "16");
+    assert_price_eq(&total(WETH, "up"), "20");
+    assert_price_eq(&total(WETH, "down"), "22");
+    assert_price_eq(&total(GRT, "up"), "35");
+    assert_price_eq(&total(GRT, "down"), "38");
+}
@@ -0,0 +1,262 @@
+use substreams::scalar::BigInt;
+use uniswap_pricing_lib::prices::{get_liquidity_drain, get_trusted_usd_price};
+
+use super::{
+    assert_price_eq,
+    blocks::{units, BlockBuilder},
+    memory_store::MemoryStore,
+    pipeline::Chain,
+    tokens::*,
+};
+use crate::{
+    modules::{
+        map_liquidity_drains::liquidity_drains, map_liquidity_windows::liquidity_windows,
+        map_pair_reserves::pair_reserves, store_flagged_tokens::set_flagged_tokens,
+        store_liquidity_windows::set_liquidity_windows, store_lp_supply::add_lp_supply,
+    },
+    pb::uniswap_pricing::v1::{
+        LiquidityDrain, LiquidityDrains, LiquidityWindows, ReserveSnapshot, ReserveSnapshots,
+    },
+    store_key_manager::StoreKey,
+    types::reserve_change::{ReserveChange, DEFAULT_WINDOW_BLOCKS},
+};
+
+// First block of a window, so the tests below all fit in it
+const BLOCK_NUMBER: u64 = 10008350;
+const BLOCK_TIMESTAMP: i64 = 1588710080;
+const MAX_DROP_PERCENT: f64 = 50.0;
+
+const NEW: &str = "00000000000000000000000000000000000000b1";
+const NEW_WETH: &str = "00000000000000000000000000000000000000c1";
+const NEW_USDC: &str = "00000000000000000000000000000000000000c2";
+const HOLDER: &str = "00000000000000000000000000000000000000d1";
+
+// `Chain` along with the stores `map_liquidity_drains` reads on top of it
+struct Drains {
+    chain: Chain,
+    windows_store: MemoryStore<ReserveSnapshot>,
+    lp_supply_store: MemoryStore<BigInt>,
+    flagged_store: MemoryStore<LiquidityDrain>,
+}
+
+impl Drains {
+    // 2,000 USDC per WETH, and a NEW/WETH pair funded with 1,000,000 NEW and 100 WETH for 10,000
+    // LP tokens
+    fn new() -> Self {
+        let mut tokens = mainnet_tokens();
+        tokens.push(erc20(NEW, "NEW", 18));
+
+        let drains = Drains {
+            chain: Chain::new(&tokens, BLOCK_NUMBER, BLOCK_TIMESTAMP),
+            windows_store: MemoryStore::default(),
+            lp_supply_store: MemoryStore::default(),
+            flagged_store: MemoryStore::default(),
+        };
+        drains.process(|block| {
+            block
+                .pair_created(USDC, WETH, USDC_WETH)
+                .sync(USDC_WETH, units(2_000_000, 6), units(1_000, 18))
+                .pair_created(NEW, WETH, NEW_WETH)
+                .first_mint(
+                    NEW_WETH,
+                    units(1_000_000, 18),
+                    units(100, 18),
+                    units(1_000_000, 18),
+                    units(100, 18),
+                )
+        });
+        drains.next_block()
+    }
+
+    fn process(&self, build: impl FnOnce(BlockBuilder) -> BlockBuilder) -> Vec<LiquidityDrain> {
+        let (blk, _) = self.chain.process(build);
+        let pipeline = &self.chain.pipeline;
+
+        let snapshots = pair_reserves(&blk, &pipeline.pairs_store);
+        add_lp_supply(
+            ReserveSnapshots {
+                items: snapshots.clone(),
+            },
+            &self.lp_supply_store,
+        );
+        let changes = ReserveChange::from_deltas(self.chain.reserve_deltas(snapshots));
+        set_liquidity_windows(
+            LiquidityWindows {
+                items: liquidity_windows(DEFAULT_WINDOW_BLOCKS, &changes),
+            },
+            &self.windows_store,
+        );
+
+        let drains = liquidity_drains(
+            &pipeline.stable_prices(&blk),
+            MAX_DROP_PERCENT,
+            &blk,
+            &changes,
+            &self.windows_store,
+            &self.lp_supply_store,
+            &self.chain.prices_store,
+        );
+        set_flagged_tokens(
+            LiquidityDrains {
+                items: drains.clone(),
+            },
+            &self.flagged_store,
+        );
+        drains
+    }
+
+    fn next_block(mut self) -> Self {
+        self.chain.next_block();
+        self
+    }
+}
+
+#[test]
+fn flags_a_pair_drained_in_one_block() {
+    let drains = Drains::new();
+    let items = drains.process(|block| {
+        block
+            .remove_liquidity(
+                NEW_WETH,
+                HOLDER,
+                units(9_000, 18),
+                units(900_000, 18),
+                units(90, 18),
+            )
+            .sync(NEW_WETH, units(100_000, 18), units(10, 18))
+    });
+
+    assert_eq!(items.len(), 1);
+    let drain = &items[0];
+    assert_eq!(drain.pair_address, NEW_WETH);
+    assert_price_eq(&drain.drop_percent, "90");
+    assert_price_eq(&drain.reserve0, "100000");
+    assert_price_eq(&drain.reserve1, "10");
+    // 90 WETH at $2,000
+    assert_price_eq(&drain.quote_drop_percent, "90");
+    assert_price_eq(&drain.quote_drop_usd, "180000");
+
+    // Burnt to the router, but on behalf of the holder who sent it 9,000 of the 10,000 LP tokens
+    assert_eq!(drain.top_remover, HOLDER);
+    assert_price_eq(&drain.top_remover_percent, "90");
+    assert_eq!(
+        drains
+            .lp_supply_store
+            .value(&StoreKey::lp_supply(NEW_WETH))
+            .map(|supply| supply.to_string()),
+        Some(units(1_000, 18).to_string())
+    );
+
+    let flagged: Vec<&str> = drain
+        .flagged_tokens
+        .iter()
+        .map(|token| token.address.as_str())
+        .collect();
+    assert_eq!(flagged, vec![NEW]);
+}
+
+#[test]
+fn flags_a_pair_drained_over_several_blocks() {
+    let drains = Drains::new();
+    let items = drains.process(|block| block.sync(NEW_WETH, units(700_000, 18), units(70, 18)));
+    assert!(items.is_empty());
+
+    let drains = drains.next_block();
+    let items = drains.process(|block| block.sync(NEW_WETH, units(490_000, 18), units(49, 18)));
+
+    assert_eq!(items.len(), 1);
+    // 30% in each block, compounding to 51% of the liquidity at the start of the window
+    assert_price_eq(&items[0].drop_percent, "51");
+    assert!(items[0].top_remover.is_empty());
+}
+
+#[test]
+fn does_not_flag_small_swaps() {
+    let drains = Drains::new();
+    let items = drains.process(|block| {
+        block.swap(
+            NEW_WETH,
+            NEW,
+            WETH,
+            units(250_000, 18),
+            units(20, 18),
+            units(1_250_000, 18),
+            units(80, 18),
+        )
+    });
+    assert!(items.is_empty());
+}
+
+#[test]
+fn flags_a_dump_draining_the_weth_reserve() {
+    let drains = Drains::new();
+    // Selling freshly minted NEW keeps the constant product but takes 75 of the 100 WETH
+    let items = drains.process(|block| {
+        block.swap(
+            NEW_WETH,
+            NEW,
+            WETH,
+            units(3_000_000, 18),
+            units(75, 18),
+            units(4_000_000, 18),
+            units(25, 18),
+        )
+    });
+
+    assert_eq!(items.len(), 1);
+    let drain = &items[0];
+    assert_price_eq(&drain.drop_percent, "0");
+    assert_price_eq(&drain.quote_drop_percent, "75");
+    assert_price_eq(&drain.quote_drop_usd, "150000");
+    assert_eq!(drain.flagged_tokens.len(), 1);
+    assert_eq!(drain.flagged_tokens[0].address, NEW);
+}
+
+#[test]
+fn only_flags_tokens_priced_from_the_drained_pair() {
+    let drains = Drains::new();
+    drains.process(|block| {
+        block.pair_created(NEW, USDC, NEW_USDC).first_mint(
+            NEW_USDC,
+            units(1_000_000, 18),
+            units(20_000, 6),
+            units(1_000_000, 18),
+            units(20_000, 6),
+        )
+    });
+
+    // NEW is priced from NEW/USDC, synced after NEW/WETH is drained
+    let drains = drains.next_block();
+    let items = drains.process(|block| {
+        block
+            .sync(NEW_WETH, units(100_000, 18), units(10, 18))
+            .sync(NEW_USDC, units(1_000_000, 18), units(20_000, 6))
+    });
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].pair_address, NEW_WETH);
+    assert!(items[0].flagged_tokens.is_empty());
+    assert!(get_liquidity_drain(&drains.flagged_store, NEW).is_none());
+}
+
+#[test]
+fn stops_trusting_the_price_of_flagged_tokens() {
+    let drains = Drains::new();
+    assert!(
+        get_trusted_usd_price(&drains.chain.prices_store, &drains.flagged_store, NEW).is_some()
+    );
+
+    drains.process(|block| block.sync(NEW_WETH, units(100_000, 18), units(10, 18)));
+
+    assert_eq!(
+        get_liquidity_drain(&drains.flagged_store, NEW).map(|drain| drain.pair_address),
+        Some(NEW_WETH.to_string())
+    );
+    assert!(
+        get_trusted_usd_price(&drains.chain.prices_store, &drains.flagged_store, NEW).is_none()
+    );
+    assert!(get_liquidity_drain(&drains.flagged_store, WETH).is_none());
+    assert!(
+        get_trusted_usd_price(&drains.chain.prices_store, &drains.flagged_store, WETH).is_some()
+    );
+}
@@ -0,0 +1,73 @@
+use substreams_entity_change::{
+    pb::entity::{value::Typed, EntityChange},
+    tables::Tables,
+};
+
+use super::{price_delta, tokens::*};
+use crate::{
+    modules::graph_out::set_prices,
+    pb::uniswap_pricing::v1::{erc20_price::Source, Erc20Price},
+};
+
+fn price(price_usd: &str, price_eth: &str) -> Erc20Price {
+    Erc20Price {
+        token: Some(erc20(GRT, "GRT", 18)),
+        price_usd: price_usd.to_string(),
+        price_eth: price_eth.to_string(),
+        pair_address: WETH_GRT.to_string(),
+        block_number: 10008355,
+        ordinal: 2,
+        source: Source::Chainlink as i32,
+        ..Default::default()
+    }
+}
+
+fn token_price(changes: &[EntityChange]) -> &EntityChange {
+    changes
+        .iter()
+        .find(|change| change.entity == "TokenPrice")
+        .expect("no TokenPrice change")
+}
+
+fn price_eth(change: &EntityChange) -> Option<Option<&Typed>> {
+    change
+        .fields
+        .iter()
+        .find(|field| field.name == "priceETH")
+        .map(|field| {
+            field
+                .new_value
+                .as_ref()
+                .and_then(|value| value.typed.as_ref())
+        })
+}
+
+#[test]
+fn sets_the_eth_price() {
+    let mut tables = Tables::new();
+    set_prices(
+        &mut tables,
+        vec![price_delta(None, price("0.5", "0.00025"))],
+    );
+
+    let changes = tables.to_entity_changes().entity_changes;
+
+    assert!(matches!(
+        price_eth(token_price(&changes)),
+        Some(Some(Typed::Bigdecimal(price_eth))) if price_eth == "0.00025"
+    ));
+}
+
+#[test]
+fn sets_a_missing_eth_price_to_null() {
+    let mut tables = Tables::new();
+    set_prices(
+        &mut tables,
+        vec![price_delta(Some(price("0.4", "0.0002")), price("0.5", ""))],
+    );
+
+    let changes = tables.to_entity_changes().entity_changes;
+
+    // Written as null rather than left out, so the previous ETH price is cleared
+    assert!(matches!(price_eth(token_price(&changes)), Some(None)));
+}
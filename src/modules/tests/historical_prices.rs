@@ -0,0 +1,61 @@
+use prost_types::Timestamp;
+
+use super::{memory_store::MemoryStore, price_delta, tokens::*};
+use crate::{
+    modules::store_historical_prices::set_historical_prices, pb::uniswap_pricing::v1::Erc20Price,
+    store_key_manager::StoreKey,
+};
+
+// 2020-12-31T00:00:00Z
+const DAY_START: i64 = 1609372800;
+
+fn price(price_usd: &str, seconds: i64) -> Erc20Price {
+    Erc20Price {
+        token: Some(erc20(GRT, "GRT", 18)),
+        price_usd: price_usd.to_string(),
+        block_time: Some(Timestamp { seconds, nanos: 0 }),
+        ..Default::default()
+    }
+}
+
+fn price_usd(store: &MemoryStore<Erc20Price>, key: &str) -> Option<String> {
+    store.value(key).map(|price| price.price_usd)
+}
+
+#[test]
+fn keeps_the_closing_price_of_every_day_and_hour() {
+    let store = MemoryStore::default();
+    let deltas = vec![
+        price_delta(None, price("0.1", DAY_START + 60)),
+        price_delta(Some(price("0.1", 0)), price("0.2", DAY_START + 3599)),
+        price_delta(Some(price("0.2", 0)), price("0.3", DAY_START + 3600)),
+        price_delta(Some(price("0.3", 0)), price("0.4", DAY_START + 86399)),
+        price_delta(Some(price("0.4", 0)), price("0.5", DAY_START + 86400)),
+    ];
+
+    set_historical_prices(deltas, &store);
+
+    let daily = |day: &str| price_usd(&store, &StoreKey::daily_price_by_address(GRT, day));
+    let hourly = |hour: &str| price_usd(&store, &StoreKey::hourly_price_by_address(GRT, hour));
+    assert_eq!(daily("2020-12-31").as_deref(), Some("0.4"));
+    assert_eq!(daily("2021-01-01").as_deref(), Some("0.5"));
+    assert_eq!(hourly("2020-12-31T00").as_deref(), Some("0.2"));
+    assert_eq!(hourly("2020-12-31T01").as_deref(), Some("0.3"));
+    assert_eq!(hourly("2020-12-31T23").as_deref(), Some("0.4"));
+    assert_eq!(hourly("2021-01-01T00").as_deref(), Some("0.5"));
+    assert_eq!(hourly("2020-12-31T02"), None);
+}
+
+#[test]
+fn ignores_symbol_keyed_prices() {
+    let store = MemoryStore::default();
+    let mut delta = price_delta(None, price("0.1", DAY_START));
+    delta.key = StoreKey::usd_price_by_symbol("GRT");
+
+    set_historical_prices(vec![delta], &store);
+
+    assert_eq!(
+        price_usd(&store, &StoreKey::daily_price_by_address(GRT, "2020-12-31")),
+        None
+    );
+}
@@ -0,0 +1,72 @@
+use super::{
+    blocks::{units, BlockBuilder},
+    tokens::*,
+};
+use crate::modules::index_uniswap_events::{uniswap_event_keys, PAIR_CREATED_KEY, SYNC_KEY};
+
+#[test]
+fn indexes_nothing_for_blocks_without_uniswap_logs() {
+    let keys = uniswap_event_keys(&BlockBuilder::new(10008355, 1588710145).build());
+    assert!(keys.keys.is_empty());
+}
+
+#[test]
+fn indexes_pair_created_and_sync_logs() {
+    let keys = uniswap_event_keys(
+        &BlockBuilder::new(10008355, 1588710145)
+            .pair_created(USDC, WETH, USDC_WETH)
+            .sync(USDC_WETH, units(2_000_000, 6), units(1_000, 18))
+            .build(),
+    );
+    assert_eq!(keys.keys, vec![PAIR_CREATED_KEY, SYNC_KEY]);
+}
+
+// The factory's USDC/WETH pair, with USDC as token0
+const MAINNET_USDC_WETH: &str = "b4e16d0168e52d35cacd2c6185b44281ec28c9dc";
+
+#[test]
+fn indexes_swaps_of_factory_pairs() {
+    let keys = uniswap_event_keys(
+        &BlockBuilder::new(10008355, 1588710145)
+            .swap(
+                MAINNET_USDC_WETH,
+                USDC,
+                WETH,
+                units(2_000, 6),
+                units(1, 18),
+                units(2_002_000, 6),
+                units(999, 18),
+            )
+            .build(),
+    );
+    assert_eq!(keys.keys, vec![SYNC_KEY]);
+}
+
+#[test]
+fn skips_swaps_of_pairs_from_other_factories() {
+    // Same tokens, at an address the factory would not deploy their pair to
+    let keys = uniswap_event_keys(
+        &BlockBuilder::new(10008355, 1588710145)
+            .swap(
+                USDC_WETH,
+                USDC,
+                WETH,
+                units(2_000, 6),
+                units(1, 18),
+                units(2_002_000, 6),
+                units(999, 18),
+            )
+            .build(),
+    );
+    assert!(keys.keys.is_empty());
+}
+
+#[test]
+fn indexes_sync_logs_it_cannot_match_to_a_pair() {
+    let keys = uniswap_event_keys(
+        &BlockBuilder::new(10008355, 1588710145)
+            .sync(DAI_WETH, units(2_000_000, 18), units(1_000, 18))
+            .build(),
+    );
+    assert_eq!(keys.keys, vec![SYNC_KEY]);
+}
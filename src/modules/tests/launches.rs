@@ -0,0 +1,190 @@
+use substreams::Hex;
+
+use super::{
+    assert_price_eq,
+    blocks::{units, BlockBuilder, TX_FROM},
+    memory_store::MemoryStore,
+    pipeline::Chain,
+    tokens::*,
+};
+use crate::{
+    modules::{
+        map_pair_created_events::pair_created_events, map_token_launches::token_launches,
+        store_first_pairs::set_first_pairs,
+    },
+    pb::uniswap_pricing::v1::{PairCreated, TokenLaunch},
+};
+
+const BLOCK_NUMBER: u64 = 10008355;
+const BLOCK_TIMESTAMP: i64 = 1588710145;
+
+const NEW: &str = "00000000000000000000000000000000000000b1";
+const NEW_WETH: &str = "00000000000000000000000000000000000000c1";
+const NEW_USDC: &str = "00000000000000000000000000000000000000c2";
+
+// `Chain` along with the store `map_token_launches` reads on top of it
+struct Launches {
+    chain: Chain,
+    first_pairs_store: MemoryStore<PairCreated>,
+}
+
+impl Launches {
+    fn new() -> Self {
+        let mut tokens = mainnet_tokens();
+        tokens.push(erc20(NEW, "NEW", 18));
+
+        let launches = Launches {
+            chain: Chain::new(&tokens, BLOCK_NUMBER, BLOCK_TIMESTAMP),
+            first_pairs_store: MemoryStore::default(),
+        };
+        // 2,000 USDC per WETH
+        launches.process(|block| {
+            block.pair_created(USDC, WETH, USDC_WETH).sync(
+                USDC_WETH,
+                units(2_000_000, 6),
+                units(1_000, 18),
+            )
+        });
+        launches
+    }
+
+    fn process(&self, build: impl FnOnce(BlockBuilder) -> BlockBuilder) -> Vec<TokenLaunch> {
+        let (blk, output) = self.chain.process(build);
+        let pipeline = &self.chain.pipeline;
+        let events = pair_created_events(&blk, |address| {
+            pipeline.tokens.get(&Hex::encode(address)).cloned()
+        });
+        set_first_pairs(events, &self.first_pairs_store);
+
+        token_launches(
+            &pipeline.stable_prices(&blk),
+            &blk,
+            &output.uniswap_prices,
+            &pipeline.pairs_store,
+            &self.first_pairs_store,
+            &self.chain.prices_store,
+        )
+    }
+
+    fn next_block(mut self) -> Self {
+        self.chain.next_block();
+        self
+    }
+}
+
+#[test]
+fn launches_a_token_on_the_first_mint_of_its_first_pair() {
+    let launches = Launches::new().next_block();
+    let items = launches.process(|block| {
+        block.pair_created(NEW, WETH, NEW_WETH).first_mint(
+            NEW_WETH,
+            units(1_000_000, 18),
+            units(10, 18),
+            units(1_000_000, 18),
+            units(10, 18),
+        )
+    });
+
+    assert_eq!(items.len(), 1);
+    let launch = &items[0];
+    assert_eq!(launch.token_ref().address, NEW);
+
+    let pair = launch.pair.as_ref().unwrap();
+    assert_eq!(pair.pair_address, NEW_WETH);
+    assert_eq!(pair.tx_from, TX_FROM);
+    assert_eq!(launch.block_number, BLOCK_NUMBER + 1);
+
+    assert_price_eq(&launch.token_amount, "1000000");
+    assert_price_eq(&launch.paired_token_amount, "10");
+    // 10 WETH at $2,000, on both sides of the pair
+    assert_price_eq(&launch.initial_liquidity_usd, "40000");
+    assert_price_eq(&launch.first_price.as_ref().unwrap().price_usd, "0.02");
+}
+
+#[test]
+fn does_not_launch_on_later_mints() {
+    let launches = Launches::new().next_block();
+    launches.process(|block| {
+        block.pair_created(NEW, WETH, NEW_WETH).first_mint(
+            NEW_WETH,
+            units(1_000_000, 18),
+            units(10, 18),
+            units(1_000_000, 18),
+            units(10, 18),
+        )
+    });
+
+    let launches = launches.next_block();
+    let items = launches.process(|block| {
+        block
+            .sync(NEW_WETH, units(2_000_000, 18), units(20, 18))
+            .mint(NEW_WETH, units(1_000_000, 18), units(10, 18))
+    });
+    assert!(items.is_empty());
+}
+
+#[test]
+fn only_launches_a_token_from_its_first_pair() {
+    let launches = Launches::new().next_block();
+    launches.process(|block| block.pair_created(NEW, WETH, NEW_WETH));
+
+    let launches = launches.next_block();
+    let items = launches.process(|block| {
+        block.pair_created(NEW, USDC, NEW_USDC).first_mint(
+            NEW_USDC,
+            units(1_000_000, 18),
+            units(20_000, 6),
+            units(1_000_000, 18),
+            units(20_000, 6),
+        )
+    });
+    assert!(items.is_empty());
+}
+
+#[test]
+fn never_launches_weth_or_stablecoins() {
+    let launches = Launches::new().next_block();
+    let items = launches.process(|block| {
+        block.pair_created(DAI, WETH, DAI_WETH).first_mint(
+            DAI_WETH,
+            units(2_000_000, 18),
+            units(1_000, 18),
+            units(2_000_000, 18),
+            units(1_000, 18),
+        )
+    });
+    assert!(items.is_empty());
+}
+
+#[test]
+fn launches_a_pair_funded_before_its_first_mint() {
+    let launches = Launches::new().next_block();
+    // 1 WETH is sent to the pair and synced before liquidity is added
+    let items = launches.process(|block| {
+        block
+            .pair_created(NEW, WETH, NEW_WETH)
+            .sync(NEW_WETH, 0, units(1, 18))
+            .first_mint(
+                NEW_WETH,
+                units(1_000_000, 18),
+                units(10, 18),
+                units(1_000_000, 18),
+                units(11, 18),
+            )
+    });
+
+    assert_eq!(items.len(), 1);
+    assert_price_eq(&items[0].paired_token_amount, "10");
+}
+
+#[test]
+fn does_not_launch_on_a_mint_without_the_minimum_liquidity_lock() {
+    let launches = Launches::new().next_block();
+    let items = launches.process(|block| {
+        block
+            .pair_created(NEW, WETH, NEW_WETH)
+            .sync(NEW_WETH, units(1_000_000, 18), units(10, 18))
+            .mint(NEW_WETH, units(1_000_000, 18), units(10, 18))
+    });
+    assert!(items.is_empty());
+}
@@ -0,0 +1,131 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ops::Add,
+};
+
+use substreams::store::{StoreAdd, StoreDelete, StoreGet, StoreNew, StoreSet, StoreSetIfNotExists};
+
+// In-memory stand-in for the substreams stores, so modules can be run natively. Ordinals are
+// ignored: every read sees the latest value, which is what `get_last` returns at the end of a
+// block. Reads through `StoreGet` are counted, as each one is a host call in a real run.
+pub struct MemoryStore<T> {
+    values: RefCell<HashMap<String, T>>,
+    reads: Cell<usize>,
+}
+
+impl<T> Default for MemoryStore<T> {
+    fn default() -> Self {
+        MemoryStore {
+            values: RefCell::new(HashMap::new()),
+            reads: Cell::new(0),
+        }
+    }
+}
+
+impl<T: Clone> MemoryStore<T> {
+    pub fn insert(&self, key: impl Into<String>, value: T) {
+        self.values.borrow_mut().insert(key.into(), value);
+    }
+
+    pub fn value(&self, key: &str) -> Option<T> {
+        self.values.borrow().get(key).cloned()
+    }
+
+    pub fn reads(&self) -> usize {
+        self.reads.get()
+    }
+
+    fn read(&self, key: &str) -> Option<T> {
+        self.reads.set(self.reads.get() + 1);
+        self.value(key)
+    }
+}
+
+impl<T> StoreNew for MemoryStore<T> {
+    fn new() -> Self {
+        MemoryStore::default()
+    }
+}
+
+impl<T> StoreDelete for MemoryStore<T> {
+    fn delete_prefix(&self, _ord: i64, prefix: &String) {
+        self.values
+            .borrow_mut()
+            .retain(|key, _| !key.starts_with(prefix.as_str()));
+    }
+}
+
+impl<T: Clone> StoreSet<T> for MemoryStore<T> {
+    fn set<K: AsRef<str>>(&self, _ord: u64, key: K, value: &T) {
+        self.insert(key.as_ref(), value.clone());
+    }
+
+    fn set_many<K: AsRef<str>>(&self, ord: u64, keys: &Vec<K>, value: &T) {
+        for key in keys {
+            self.set(ord, key, value);
+        }
+    }
+}
+
+impl<T: Clone> StoreSetIfNotExists<T> for MemoryStore<T> {
+    fn set_if_not_exists<K: AsRef<str>>(&self, _ord: u64, key: K, value: &T) {
+        self.values
+            .borrow_mut()
+            .entry(key.as_ref().to_string())
+            .or_insert_with(|| value.clone());
+    }
+
+    fn set_if_not_exists_many<K: AsRef<str>>(&self, ord: u64, keys: &Vec<K>, value: &T) {
+        for key in keys {
+            self.set_if_not_exists(ord, key, value);
+        }
+    }
+}
+
+impl<T: Clone + Add<Output = T>> StoreAdd<T> for MemoryStore<T> {
+    fn add<K: AsRef<str>>(&self, _ord: u64, key: K, value: T) {
+        let mut values = self.values.borrow_mut();
+        let total = match values.remove(key.as_ref()) {
+            Some(total) => total + value,
+            None => value,
+        };
+        values.insert(key.as_ref().to_string(), total);
+    }
+
+    fn add_many<K: AsRef<str>>(&self, ord: u64, keys: &Vec<K>, value: T) {
+        for key in keys {
+            self.add(ord, key, value.clone());
+        }
+    }
+}
+
+impl<T: Clone> StoreGet<T> for MemoryStore<T> {
+    fn new(_idx: u32) -> Self {
+        MemoryStore::default()
+    }
+
+    fn get_at<K: AsRef<str>>(&self, _ord: u64, key: K) -> Option<T> {
+        self.read(key.as_ref())
+    }
+
+    fn get_last<K: AsRef<str>>(&self, key: K) -> Option<T> {
+        self.read(key.as_ref())
+    }
+
+    fn get_first<K: AsRef<str>>(&self, key: K) -> Option<T> {
+        self.read(key.as_ref())
+    }
+
+    fn has_at<K: AsRef<str>>(&self, _ord: u64, key: K) -> bool {
+        self.has_last(key)
+    }
+
+    fn has_last<K: AsRef<str>>(&self, key: K) -> bool {
+        self.read(key.as_ref()).is_some()
+    }
+
+    fn has_first<K: AsRef<str>>(&self, key: K) -> bool {
+        self.has_last(key)
+    }
+}
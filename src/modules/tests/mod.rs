@@ -0,0 +1,87 @@
+// Native test harness: modules are run over synthetic blocks with in-memory stores, see
+// `Pipeline`.
+mod block_context;
+mod blocks;
+mod canonical_symbols;
+mod ch_out;
+mod chainlink_prices;
+mod db_out;
+mod depth;
+mod drains;
+mod graph_out;
+mod historical_prices;
+mod index;
+mod launches;
+mod memory_store;
+mod pipeline;
+mod price_changes;
+mod price_deviations;
+mod pricing;
+mod quote;
+mod quote_prices;
+mod scenarios;
+mod stale_prices;
+mod tokens;
+mod uniswap_prices;
+
+use std::str::FromStr;
+
+use substreams::{pb::substreams::store_delta::Operation, scalar::BigDecimal, store::DeltaProto};
+
+use crate::{
+    pb::{
+        chainlink::v1::{AssetPair, Erc20Token, Price},
+        uniswap_pricing::v1::Erc20Price,
+    },
+    store_key_manager::StoreKey,
+};
+
+// Asserts a decimal price string equals the expected value, up to 1e-12 of rounding.
+pub fn assert_price_eq(actual: &str, expected: &str) {
+    let actual_value = BigDecimal::from_str(actual).expect("invalid price");
+    let expected_value = BigDecimal::from_str(expected).expect("invalid expected price");
+    let difference = actual_value - expected_value;
+    let tolerance = BigDecimal::from_str("0.000000000001").unwrap();
+
+    assert!(
+        difference <= tolerance && BigDecimal::zero() - difference <= tolerance,
+        "expected price {}, got {}",
+        expected,
+        actual
+    );
+}
+
+// Chainlink `<base>/USD` answer as kept in `store_chainlink_answers`.
+pub fn usd_answer(base: &str, price: &str, timestamp: i64) -> Price {
+    let token = |symbol: &str| Erc20Token {
+        symbol: symbol.to_string(),
+        ..Default::default()
+    };
+
+    Price {
+        asset_pair: Some(AssetPair {
+            base_token: Some(token(base)),
+            quote_token: Some(token("USD")),
+            ..Default::default()
+        }),
+        price: price.to_string(),
+        timestamp,
+        ..Default::default()
+    }
+}
+
+// Delta of `store_uniswap_prices` under the address key of the token, creating the price when
+// there is no old one.
+pub fn price_delta(old_value: Option<Erc20Price>, new_value: Erc20Price) -> DeltaProto<Erc20Price> {
+    DeltaProto {
+        operation: if old_value.is_some() {
+            Operation::Update
+        } else {
+            Operation::Create
+        },
+        ordinal: 0,
+        key: StoreKey::usd_price_by_address(&new_value.token_ref().address),
+        old_value: old_value.unwrap_or_default(),
+        new_value,
+    }
+}
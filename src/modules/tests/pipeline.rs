@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use substreams::{pb::substreams::store_delta::Operation, store::DeltaProto, Hex};
+use substreams_ethereum::pb::eth::v2 as eth;
+
+use super::{blocks::BlockBuilder, memory_store::MemoryStore};
+use crate::{
+    modules::{
+        map_pair_created_events::pair_created_events, map_uniswap_prices::uniswap_prices,
+        map_weth_prices::weth_prices, store_pair_created_events::set_pair_created_events,
+        store_weth_prices::set_weth_prices,
+    },
+    params::Params,
+    pb::{
+        chainlink::v1::Price,
+        uniswap_pricing::v1::{Erc20Price, Erc20Prices, Erc20Token, PairCreated, ReserveSnapshot},
+    },
+    store_key_manager::StoreKey,
+    types::stable_coin::{StableCoins, StablePrices},
+};
+
+pub const ETH_USD_HEARTBEAT_SECONDS: i64 = 3600;
+pub const STABLECOIN_HEARTBEAT_SECONDS: i64 = 86400;
+
+// Runs blocks through `map_pair_created_events` → `store_pair_created_events` →
+// `map_stable_coins` → `map_weth_prices` → `store_weth_prices` → `map_uniswap_prices`, keeping the
+// stores in memory between blocks. Token metadata comes from the registered tokens instead of RPC calls.
+pub struct Pipeline {
+    pub stable_coins: StableCoins,
+    pub tokens: HashMap<String, Erc20Token>,
+    pub pairs_store: MemoryStore<PairCreated>,
+    pub weth_price_store: MemoryStore<Erc20Price>,
+    pub chainlink_answers_store: MemoryStore<Price>,
+}
+
+// Outputs of the map modules for a single block
+pub struct BlockOutput {
+    pub weth_prices: Erc20Prices,
+    pub uniswap_prices: Erc20Prices,
+}
+
+// `Pipeline` over consecutive blocks 13 seconds apart, for the modules that read further stores on
+// top of it. `store_uniswap_prices` is kept from the Uniswap prices of each block, and the deltas of
+// `store_pair_reserves` are emulated from the values it held before each block.
+pub struct Chain {
+    pub pipeline: Pipeline,
+    pub prices_store: MemoryStore<Erc20Price>,
+    pub reserves_store: MemoryStore<ReserveSnapshot>,
+    pub block_number: u64,
+    first_block_number: u64,
+    first_block_timestamp: i64,
+}
+
+impl Pipeline {
+    pub fn new(tokens: &[Erc20Token]) -> Self {
+        Pipeline {
+            stable_coins: StableCoins::from_params(&Params::parse("")).unwrap(),
+            tokens: tokens
+                .iter()
+                .map(|token| (token.address.clone(), token.clone()))
+                .collect(),
+            pairs_store: MemoryStore::default(),
+            weth_price_store: MemoryStore::default(),
+            chainlink_answers_store: MemoryStore::default(),
+        }
+    }
+
+    pub fn process(&self, blk: &eth::Block) -> BlockOutput {
+        let events = pair_created_events(blk, |address| {
+            self.tokens.get(&Hex::encode(address)).cloned()
+        });
+        set_pair_created_events(events, &self.pairs_store);
+        let stable_prices = self.stable_prices(blk);
+
+        let weth_prices = Erc20Prices {
+            items: weth_prices(&stable_prices, blk, &self.pairs_store),
+        };
+        set_weth_prices(weth_prices.clone(), &self.weth_price_store);
+
+        let uniswap_prices = Erc20Prices {
+            items: uniswap_prices(
+                &stable_prices,
+                ETH_USD_HEARTBEAT_SECONDS,
+                blk,
+                &self.pairs_store,
+                &self.weth_price_store,
+                &self.chainlink_answers_store,
+            ),
+        };
+
+        BlockOutput {
+            weth_prices,
+            uniswap_prices,
+        }
+    }
+
+    // What `map_stable_coins` emits for the block
+    pub fn stable_prices(&self, blk: &eth::Block) -> StablePrices {
+        StablePrices::from_proto(&self.stable_coins.prices(
+            &self.chainlink_answers_store,
+            blk.timestamp().seconds,
+            STABLECOIN_HEARTBEAT_SECONDS,
+        ))
+    }
+}
+
+impl Chain {
+    pub fn new(tokens: &[Erc20Token], block_number: u64, block_timestamp: i64) -> Self {
+        Chain {
+            pipeline: Pipeline::new(tokens),
+            prices_store: MemoryStore::default(),
+            reserves_store: MemoryStore::default(),
+            block_number,
+            first_block_number: block_number,
+            first_block_timestamp: block_timestamp,
+        }
+    }
+
+    // Builds the current block and runs it through the pipeline
+    pub fn process(
+        &self,
+        build: impl FnOnce(BlockBuilder) -> BlockBuilder,
+    ) -> (eth::Block, BlockOutput) {
+        let offset = self.block_number - self.first_block_number;
+        let blk: eth::Block = build(BlockBuilder::new(
+            self.block_number,
+            self.first_block_timestamp + 13 * offset as i64,
+        ))
+        .build();
+
+        let output = self.pipeline.process(&blk);
+        for price in &output.uniswap_prices.items {
+            self.prices_store.insert(
+                StoreKey::usd_price_by_address(&price.token_ref().address),
+                price.clone(),
+            );
+        }
+
+        (blk, output)
+    }
+
+    // Deltas `store_pair_reserves` emits for the snapshots of a block, under both of its keys
+    pub fn reserve_deltas(
+        &self,
+        snapshots: Vec<ReserveSnapshot>,
+    ) -> Vec<DeltaProto<ReserveSnapshot>> {
+        let mut deltas = Vec::new();
+
+        for snapshot in snapshots {
+            let keys = [
+                StoreKey::pair_reserves(&snapshot.pair_address),
+                StoreKey::pair_reserves_by_tokens(
+                    &snapshot.token0_ref().address,
+                    &snapshot.token1_ref().address,
+                ),
+            ];
+            for key in keys {
+                let old_value = self.reserves_store.value(&key);
+                self.reserves_store.insert(key.clone(), snapshot.clone());
+
+                deltas.push(DeltaProto {
+                    operation: if old_value.is_some() {
+                        Operation::Update
+                    } else {
+                        Operation::Create
+                    },
+                    ordinal: snapshot.ordinal,
+                    key,
+                    old_value: old_value.unwrap_or_default(),
+                    new_value: snapshot.clone(),
+                });
+            }
+        }
+
+        deltas
+    }
+
+    pub fn next_block(&mut self) {
+        self.block_number += 1;
+    }
+}
+
+impl BlockOutput {
+    // The single Uniswap price emitted for a token, panics if there is not exactly one
+    pub fn price_of(&self, token_address: &str) -> &Erc20Price {
+        let prices: Vec<&Erc20Price> = self
+            .uniswap_prices
+            .items
+            .iter()
+            .filter(|price| price.token_ref().address == token_address)
+            .collect();
+        assert_eq!(
+            prices.len(),
+            1,
+            "expected one price for {}, got {:?}",
+            token_address,
+            prices
+        );
+        prices[0]
+    }
+}
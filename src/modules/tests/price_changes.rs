@@ -0,0 +1,69 @@
+use std::str::FromStr;
+
+use substreams::{pb::substreams::store_delta::Operation, scalar::BigDecimal, store::DeltaProto};
+
+use super::{assert_price_eq, price_delta, tokens::*};
+use crate::{
+    modules::map_price_changes::price_changes,
+    pb::uniswap_pricing::v1::{Erc20Price, PriceChange},
+};
+
+const MIN_CHANGE_PERCENT: &str = "1";
+
+fn price(price_usd: &str) -> Erc20Price {
+    Erc20Price {
+        token: Some(erc20(GRT, "GRT", 18)),
+        price_usd: price_usd.to_string(),
+        ..Default::default()
+    }
+}
+
+fn run(deltas: Vec<DeltaProto<Erc20Price>>) -> Vec<PriceChange> {
+    price_changes(&BigDecimal::from_str(MIN_CHANGE_PERCENT).unwrap(), deltas)
+}
+
+#[test]
+fn reports_the_sign_of_the_change() {
+    let changes = run(vec![
+        price_delta(Some(price("0.5")), price("0.6")),
+        price_delta(Some(price("0.6")), price("0.3")),
+    ]);
+
+    assert_eq!(changes.len(), 2);
+    assert_price_eq(&changes[0].change_percent, "20");
+    assert_eq!(changes[0].old_price.as_ref().unwrap().price_usd, "0.5");
+    assert_eq!(changes[0].new_price.as_ref().unwrap().price_usd, "0.6");
+    assert_price_eq(&changes[1].change_percent, "-50");
+}
+
+#[test]
+fn leaves_out_changes_below_the_minimum_either_way() {
+    let changes = run(vec![
+        price_delta(Some(price("100")), price("100.5")),
+        price_delta(Some(price("100")), price("99.5")),
+        price_delta(Some(price("100")), price("101")),
+        price_delta(Some(price("100")), price("99")),
+    ]);
+
+    assert_eq!(changes.len(), 2);
+    assert_price_eq(&changes[0].change_percent, "1");
+    assert_price_eq(&changes[1].change_percent, "-1");
+}
+
+#[test]
+fn has_no_change_for_a_first_price() {
+    let changes = run(vec![price_delta(None, price("0.5"))]);
+
+    assert!(changes.is_empty());
+}
+
+#[test]
+fn ignores_deleted_prices() {
+    let mut delta = price_delta(Some(price("0.5")), price("0.5"));
+    delta.operation = Operation::Delete;
+    delta.new_value = Erc20Price::default();
+
+    let changes = run(vec![delta]);
+
+    assert!(changes.is_empty());
+}
@@ -0,0 +1,89 @@
+use std::str::FromStr;
+
+use substreams::scalar::BigDecimal;
+
+use super::{assert_price_eq, memory_store::MemoryStore, tokens::*, usd_answer};
+use crate::{
+    modules::map_price_deviations::price_deviations,
+    pb::{
+        chainlink::v1::Price,
+        uniswap_pricing::v1::{erc20_price::Source, Erc20Price, Erc20Prices, PriceDeviation},
+    },
+    store_key_manager::StoreKey,
+};
+
+const BLOCK_SECONDS: i64 = 1_700_000_000;
+const HEARTBEAT_SECONDS: i64 = 86400;
+const ETH_USD_HEARTBEAT_SECONDS: i64 = 3600;
+const MIN_DEVIATION_PERCENT: &str = "5";
+
+fn uniswap_price(token_address: &str, symbol: &str, price_usd: &str) -> Erc20Price {
+    Erc20Price {
+        token: Some(erc20(token_address, symbol, 18)),
+        price_usd: price_usd.to_string(),
+        pair_address: WETH_GRT.to_string(),
+        source: Source::Uniswap as i32,
+        ..Default::default()
+    }
+}
+
+fn run(prices: Vec<Erc20Price>, answers: &[(&str, &str, i64)]) -> Vec<PriceDeviation> {
+    let chainlink_answers_store: MemoryStore<Price> = MemoryStore::default();
+    for (base, answer, timestamp) in answers {
+        chainlink_answers_store.insert(
+            StoreKey::chainlink_answer(base, "USD"),
+            usd_answer(base, answer, *timestamp),
+        );
+    }
+
+    price_deviations(
+        &BigDecimal::from_str(MIN_DEVIATION_PERCENT).unwrap(),
+        HEARTBEAT_SECONDS,
+        ETH_USD_HEARTBEAT_SECONDS,
+        BLOCK_SECONDS,
+        &Erc20Prices { items: prices },
+        &chainlink_answers_store,
+    )
+}
+
+#[test]
+fn reports_deviations_above_the_threshold() {
+    let items = run(
+        vec![uniswap_price(GRT, "GRT", "0.45")],
+        &[("GRT", "0.5", BLOCK_SECONDS - 60)],
+    );
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].token.as_ref().unwrap().address, GRT);
+    assert_eq!(items[0].pair_address, WETH_GRT);
+    assert_eq!(items[0].chainlink_price_usd, "0.5");
+    assert_eq!(items[0].chainlink_timestamp, BLOCK_SECONDS - 60);
+    assert_price_eq(&items[0].deviation_percent, "-10");
+}
+
+#[test]
+fn leaves_out_deviations_below_the_threshold() {
+    let items = run(
+        vec![uniswap_price(GRT, "GRT", "0.51")],
+        &[("GRT", "0.5", BLOCK_SECONDS - 60)],
+    );
+
+    assert!(items.is_empty());
+}
+
+#[test]
+fn does_not_compare_against_a_stale_answer() {
+    let items = run(
+        vec![
+            uniswap_price(GRT, "GRT", "0.45"),
+            uniswap_price(WETH, "WETH", "1800"),
+        ],
+        &[
+            ("GRT", "0.5", BLOCK_SECONDS - HEARTBEAT_SECONDS - 1),
+            // Within the heartbeat of the other feeds, but not of ETH/USD
+            ("ETH", "2000", BLOCK_SECONDS - 2 * ETH_USD_HEARTBEAT_SECONDS),
+        ],
+    );
+
+    assert!(items.is_empty());
+}
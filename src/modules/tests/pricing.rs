@@ -0,0 +1,313 @@
+use std::str::FromStr;
+
+use hex_literal::hex;
+use substreams::scalar::{BigDecimal, BigInt};
+
+use crate::{
+    constants::WETH_ADDRESS_BYTES,
+    pricing::{
+        depth, price_pair, price_pair_with_anchors, Address, Anchor, PricingInput, Side,
+        DEPTH_PERCENTS,
+    },
+};
+
+const USDC: Address = hex!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+const GRT: Address = hex!("c944e90c64b2c07662a292be6244bdf05cda44a7");
+
+fn amount(units: u64, decimals: u32) -> BigInt {
+    BigInt::from(units) * BigInt::from(10u64.pow(decimals))
+}
+
+fn decimal(value: &str) -> BigDecimal {
+    BigDecimal::from_str(value).unwrap()
+}
+
+fn assert_close(actual: &BigDecimal, expected: &BigDecimal) {
+    let difference = actual.clone() - expected.clone();
+    let tolerance = decimal("0.000000000001");
+    assert!(
+        difference <= tolerance && BigDecimal::zero() - difference <= tolerance,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+fn input(token0: Address, token1: Address, reserve0: BigInt, reserve1: BigInt) -> PricingInput {
+    PricingInput {
+        token0,
+        token1,
+        reserve0,
+        reserve1,
+        decimals0: 18,
+        decimals1: 18,
+        base_price0: None,
+        base_price1: None,
+        eth_price: None,
+    }
+}
+
+#[test]
+fn prices_the_other_side_of_a_base_token() {
+    let prices = price_pair(PricingInput {
+        decimals0: 6,
+        base_price0: Some(BigDecimal::one()),
+        eth_price: Some(decimal("2000")),
+        ..input(USDC, GRT, amount(500, 6), amount(1_000, 18))
+    });
+
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices[0].side, Side::Token1);
+    assert_eq!(prices[0].anchor, Anchor::BaseToken);
+    assert_close(prices[0].price_usd.as_ref().unwrap(), &decimal("0.5"));
+    assert_close(prices[0].price_eth.as_ref().unwrap(), &decimal("0.00025"));
+}
+
+#[test]
+fn prices_against_weth_with_the_eth_price() {
+    let prices = price_pair(PricingInput {
+        eth_price: Some(decimal("2000")),
+        ..input(WETH_ADDRESS_BYTES, GRT, amount(10, 18), amount(100_000, 18))
+    });
+
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices[0].side, Side::Token1);
+    assert_eq!(prices[0].anchor, Anchor::Weth);
+    assert_close(prices[0].price_usd.as_ref().unwrap(), &decimal("0.2"));
+    assert_close(prices[0].price_eth.as_ref().unwrap(), &decimal("0.0001"));
+}
+
+#[test]
+fn prices_against_weth_in_eth_only_without_an_eth_price() {
+    for eth_price in [None, Some(BigDecimal::zero())] {
+        let prices = price_pair(PricingInput {
+            eth_price,
+            ..input(WETH_ADDRESS_BYTES, GRT, amount(10, 18), amount(100_000, 18))
+        });
+
+        assert_eq!(prices.len(), 1);
+        assert_eq!(prices[0].side, Side::Token1);
+        assert_eq!(prices[0].price_usd, None);
+        assert_close(prices[0].price_eth.as_ref().unwrap(), &decimal("0.0001"));
+    }
+}
+
+#[test]
+fn prices_both_sides_of_a_base_token_weth_pair() {
+    let prices = price_pair(PricingInput {
+        decimals0: 6,
+        base_price0: Some(BigDecimal::one()),
+        eth_price: Some(decimal("2000")),
+        ..input(
+            USDC,
+            WETH_ADDRESS_BYTES,
+            amount(2_000_000, 6),
+            amount(1_000, 18),
+        )
+    });
+
+    assert_eq!(prices.len(), 2);
+    assert_eq!(prices[0].side, Side::Token1);
+    assert_close(prices[0].price_usd.as_ref().unwrap(), &decimal("2000"));
+    assert_eq!(prices[1].side, Side::Token0);
+    assert_close(prices[1].price_usd.as_ref().unwrap(), &decimal("1"));
+}
+
+#[test]
+fn prices_nothing_with_an_empty_reserve() {
+    let prices = price_pair(PricingInput {
+        base_price0: Some(BigDecimal::one()),
+        ..input(USDC, GRT, BigInt::zero(), amount(1_000, 18))
+    });
+    assert!(prices.is_empty());
+}
+
+#[test]
+fn prices_stablecoins_from_the_pool_and_weth_at_the_eth_price() {
+    let prices = price_pair_with_anchors(PricingInput {
+        decimals0: 6,
+        base_price0: Some(decimal("0.99")),
+        eth_price: Some(decimal("2500")),
+        ..input(
+            USDC,
+            WETH_ADDRESS_BYTES,
+            amount(2_000_000, 6),
+            amount(1_000, 18),
+        )
+    });
+
+    assert_eq!(prices.len(), 2);
+    // At 2,000 USDC per WETH and 2,500 USD per ETH, USDC trades above its base price
+    assert_eq!(prices[0].side, Side::Token0);
+    assert_eq!(prices[0].anchor, Anchor::Weth);
+    assert_close(prices[0].price_usd.as_ref().unwrap(), &decimal("1.25"));
+    assert_close(prices[0].price_eth.as_ref().unwrap(), &decimal("0.0005"));
+    assert_eq!(prices[1].side, Side::Token1);
+    assert_eq!(prices[1].anchor, Anchor::Reference);
+    assert_close(prices[1].price_usd.as_ref().unwrap(), &decimal("2500"));
+    assert_close(prices[1].price_eth.as_ref().unwrap(), &decimal("1"));
+}
+
+#[test]
+fn values_stablecoins_at_their_base_price_when_the_pool_cannot_price_them() {
+    let prices = price_pair_with_anchors(PricingInput {
+        decimals0: 6,
+        base_price0: Some(decimal("0.99")),
+        eth_price: Some(decimal("2500")),
+        ..input(USDC, GRT, amount(500, 6), amount(1_000, 18))
+    });
+
+    assert_eq!(prices.len(), 2);
+    assert_eq!(prices[0].side, Side::Token1);
+    assert_eq!(prices[0].anchor, Anchor::BaseToken);
+    assert_close(prices[0].price_usd.as_ref().unwrap(), &decimal("0.495"));
+    assert_eq!(prices[1].side, Side::Token0);
+    assert_eq!(prices[1].anchor, Anchor::Reference);
+    assert_close(prices[1].price_usd.as_ref().unwrap(), &decimal("0.99"));
+}
+
+#[test]
+fn prices_the_other_side_of_an_anchor_against_it() {
+    let prices = price_pair_with_anchors(PricingInput {
+        eth_price: Some(decimal("2000")),
+        ..input(WETH_ADDRESS_BYTES, GRT, amount(10, 18), amount(100_000, 18))
+    });
+
+    assert_eq!(prices.len(), 2);
+    assert_eq!(prices[0].side, Side::Token1);
+    assert_eq!(prices[0].anchor, Anchor::Weth);
+    assert_close(prices[0].price_usd.as_ref().unwrap(), &decimal("0.2"));
+    assert_eq!(prices[1].side, Side::Token0);
+    assert_eq!(prices[1].anchor, Anchor::Reference);
+    assert_close(prices[1].price_usd.as_ref().unwrap(), &decimal("2000"));
+}
+
+#[test]
+fn prices_weth_against_a_base_token_without_an_eth_price() {
+    let prices = price_pair_with_anchors(PricingInput {
+        decimals0: 6,
+        base_price0: Some(BigDecimal::one()),
+        ..input(
+            USDC,
+            WETH_ADDRESS_BYTES,
+            amount(2_000_000, 6),
+            amount(1_000, 18),
+        )
+    });
+
+    assert_eq!(prices.len(), 2);
+    assert_eq!(prices[0].side, Side::Token1);
+    assert_eq!(prices[0].anchor, Anchor::BaseToken);
+    assert_close(prices[0].price_usd.as_ref().unwrap(), &decimal("2000"));
+    assert_eq!(prices[1].side, Side::Token0);
+    assert_eq!(prices[1].anchor, Anchor::Reference);
+    assert_eq!(prices[1].price_eth, None);
+}
+
+fn to_f64(value: &BigDecimal) -> f64 {
+    value.to_string().parse().unwrap()
+}
+
+#[test]
+fn depth_moves_the_price_by_the_percent() {
+    // 1,000 token0 against 2,000 token1 worth $1 each
+    let (reserve0, reserve1) = (1_000.0, 2_000.0);
+    let spot = reserve1 / reserve0;
+
+    for percent in DEPTH_PERCENTS {
+        let levels = depth(&decimal("2000"), percent);
+        let expected = 1.0 + f64::from(percent) / 100.0;
+
+        // Buying token0 with `up_usd` of token1
+        let bought1 = reserve1 + to_f64(&levels.up_usd);
+        let bought0 = reserve0 * reserve1 / bought1;
+        assert!((bought1 / bought0 / spot - expected).abs() < 1e-9);
+
+        // Selling `down_usd` of token0, worth $2 each
+        let sold0 = reserve0 + to_f64(&levels.down_usd) / 2.0;
+        let sold1 = reserve0 * reserve1 / sold0;
+        assert!((sold1 / sold0 / spot - (2.0 - expected)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn depth_scales_with_the_reserves() {
+    let small = depth(&decimal("1000"), 2);
+    let large = depth(&decimal("1000000"), 2);
+
+    assert_close(&(small.up_usd * BigDecimal::from(1000)), &large.up_usd);
+    assert!(large.down_usd > large.up_usd);
+}
+
+// Sample reserves, in whole tokens, for the properties below
+const RESERVES: [(u64, u64); 5] = [
+    (1, 1),
+    (3, 7),
+    (1_000, 13),
+    (2_500_000, 1_234),
+    (17, 999_999_999),
+];
+
+#[test]
+fn token_order_does_not_change_prices() {
+    for (reserve_a, reserve_b) in RESERVES {
+        let forward = price_pair(PricingInput {
+            base_price0: Some(decimal("0.99")),
+            eth_price: Some(decimal("1850.5")),
+            ..input(USDC, GRT, amount(reserve_a, 18), amount(reserve_b, 18))
+        });
+        let reversed = price_pair(PricingInput {
+            base_price1: Some(decimal("0.99")),
+            eth_price: Some(decimal("1850.5")),
+            ..input(GRT, USDC, amount(reserve_b, 18), amount(reserve_a, 18))
+        });
+
+        assert_eq!(forward.len(), 1);
+        assert_eq!(reversed.len(), 1);
+        assert_eq!(forward[0].side, Side::Token1);
+        assert_eq!(reversed[0].side, Side::Token0);
+        assert_close(
+            forward[0].price_usd.as_ref().unwrap(),
+            reversed[0].price_usd.as_ref().unwrap(),
+        );
+    }
+}
+
+#[test]
+fn scaling_reserves_does_not_change_prices() {
+    for (reserve_a, reserve_b) in RESERVES {
+        let price = |scale: u64| {
+            price_pair(PricingInput {
+                eth_price: Some(decimal("1850.5")),
+                ..input(
+                    WETH_ADDRESS_BYTES,
+                    GRT,
+                    amount(reserve_a * scale, 18),
+                    amount(reserve_b * scale, 18),
+                )
+            })
+            .remove(0)
+            .price_usd
+            .unwrap()
+        };
+
+        assert_close(&price(1), &price(1_000));
+    }
+}
+
+#[test]
+fn eth_and_usd_prices_agree() {
+    for (reserve_a, reserve_b) in RESERVES {
+        let eth_price = decimal("1850.5");
+        let prices = price_pair(PricingInput {
+            decimals0: 6,
+            base_price0: Some(BigDecimal::one()),
+            eth_price: Some(eth_price.clone()),
+            ..input(USDC, GRT, amount(reserve_a, 6), amount(reserve_b, 18))
+        });
+
+        let price = &prices[0];
+        let price_eth = price.price_eth.clone().unwrap();
+        assert_close(&(price_eth * eth_price), price.price_usd.as_ref().unwrap());
+    }
+}
@@ -0,0 +1,120 @@
+use std::str::FromStr;
+
+use substreams::scalar::BigInt;
+use uniswap_pricing_lib::quote::{get_amount_out, get_amounts_out, get_pair_reserves};
+
+use super::{
+    blocks::{units, BlockBuilder},
+    memory_store::MemoryStore,
+    pipeline::Pipeline,
+    tokens::*,
+};
+use crate::{
+    modules::{map_pair_reserves::pair_reserves, store_pair_reserves::set_pair_reserves},
+    pb::uniswap_pricing::v1::{ReserveSnapshot, ReserveSnapshots},
+};
+
+const BLOCK_NUMBER: u64 = 10008355;
+const BLOCK_TIMESTAMP: i64 = 1588710145;
+
+fn int(value: &str) -> BigInt {
+    BigInt::from_str(value).unwrap()
+}
+
+// Reserves store holding 2,000,000 USDC / 1,000 WETH and 10 WETH / 100,000 GRT
+fn reserves_store() -> MemoryStore<ReserveSnapshot> {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    let blk = BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+        .pair_created(USDC, WETH, USDC_WETH)
+        .pair_created(WETH, GRT, WETH_GRT)
+        .sync(USDC_WETH, units(1_000_000, 6), units(500, 18))
+        .sync(USDC_WETH, units(2_000_000, 6), units(1_000, 18))
+        .sync(WETH_GRT, units(10, 18), units(100_000, 18))
+        .build();
+    pipeline.process(&blk);
+
+    let store = MemoryStore::default();
+    set_pair_reserves(
+        ReserveSnapshots {
+            items: pair_reserves(&blk, &pipeline.pairs_store),
+        },
+        &store,
+    );
+    store
+}
+
+#[test]
+fn quotes_a_pair_with_the_fee() {
+    let amount_out = get_amount_out(
+        &int("1000000000000000000"),
+        &int("100000000000000000000"),
+        &int("200000000000000000000"),
+    );
+
+    // 1 in for 2 out at spot, minus the fee and the price impact
+    assert_eq!(amount_out, Some(int("1974316068794122597")));
+}
+
+#[test]
+fn quotes_nothing_where_the_router_reverts() {
+    let reserve = int("1000");
+    assert_eq!(get_amount_out(&BigInt::zero(), &reserve, &reserve), None);
+    assert_eq!(get_amount_out(&reserve, &BigInt::zero(), &reserve), None);
+    assert_eq!(get_amount_out(&reserve, &reserve, &BigInt::zero()), None);
+}
+
+#[test]
+fn quotes_nothing_out_of_an_emptied_reserve() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    let blk = BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+        .pair_created(WETH, GRT, WETH_GRT)
+        .sync(WETH_GRT, units(10, 18), 0)
+        .build();
+    pipeline.process(&blk);
+
+    let store = MemoryStore::default();
+    set_pair_reserves(
+        ReserveSnapshots {
+            items: pair_reserves(&blk, &pipeline.pairs_store),
+        },
+        &store,
+    );
+
+    let amount_in = int(&units(1, 18).to_string());
+    assert_eq!(get_amounts_out(&store, &amount_in, &[WETH, GRT]), None);
+}
+
+#[test]
+fn keeps_the_latest_reserves_of_a_pair() {
+    let store = reserves_store();
+    let reserves = get_pair_reserves(&store, &format!("0x{}", USDC_WETH)).unwrap();
+
+    assert_eq!(reserves.token0_ref().address, USDC);
+    assert_eq!(reserves.reserve0, units(2_000_000, 6).to_string());
+    assert_eq!(reserves.reserve1, units(1_000, 18).to_string());
+}
+
+#[test]
+fn quotes_along_a_path_in_either_token_order() {
+    let store = reserves_store();
+    let amounts = get_amounts_out(
+        &store,
+        &int(&units(1_000, 18).to_string()),
+        &[GRT, WETH, USDC],
+    )
+    .unwrap();
+
+    // GRT is token1 of WETH/GRT, WETH token1 of USDC/WETH
+    assert_eq!(amounts.len(), 3);
+    assert_eq!(amounts[1], int("98715803439706129"));
+    assert_eq!(amounts[2], int("196819941"));
+}
+
+#[test]
+fn quotes_nothing_through_an_unknown_pair() {
+    let store = reserves_store();
+    let amount_in = int(&units(1_000, 18).to_string());
+
+    assert_eq!(get_amounts_out(&store, &amount_in, &[GRT, DAI]), None);
+    assert_eq!(get_amounts_out(&store, &amount_in, &[GRT]), None);
+}
@@ -0,0 +1,146 @@
+use super::{assert_price_eq, memory_store::MemoryStore, tokens::*, usd_answer};
+use crate::{
+    modules::map_quote_prices::quote_prices,
+    pb::{
+        chainlink::v1::Price,
+        uniswap_pricing::v1::{
+            erc20_price::Source, ChainlinkPrices, Erc20Price, Erc20Prices, QuotePrice,
+        },
+    },
+    store_key_manager::StoreKey,
+};
+
+const BLOCK_SECONDS: i64 = 1_700_000_000;
+const HEARTBEAT_SECONDS: i64 = 86400;
+
+fn price(token_address: &str, symbol: &str, price_usd: &str, source: Source) -> Erc20Price {
+    Erc20Price {
+        token: Some(erc20(token_address, symbol, 18)),
+        price_usd: price_usd.to_string(),
+        source: source as i32,
+        ..Default::default()
+    }
+}
+
+fn answers_store(answers: &[(&str, &str, i64)]) -> MemoryStore<Price> {
+    let store = MemoryStore::default();
+    for (base, answer, timestamp) in answers {
+        store.insert(
+            StoreKey::chainlink_answer(base, "USD"),
+            usd_answer(base, answer, *timestamp),
+        );
+    }
+    store
+}
+
+fn run(
+    quote_currencies: &[&str],
+    prices: Vec<Erc20Price>,
+    chainlink_prices: ChainlinkPrices,
+    chainlink_answers_store: &MemoryStore<Price>,
+) -> Vec<QuotePrice> {
+    let quote_currencies: Vec<String> = quote_currencies.iter().map(|q| q.to_string()).collect();
+    quote_prices(
+        &quote_currencies,
+        HEARTBEAT_SECONDS,
+        BLOCK_SECONDS,
+        &Erc20Prices { items: prices },
+        &chainlink_prices,
+        chainlink_answers_store,
+    )
+}
+
+#[test]
+fn converts_usd_prices_into_every_quote_currency() {
+    let store = answers_store(&[
+        ("EUR", "1.25", BLOCK_SECONDS - 60),
+        ("BTC", "50000", BLOCK_SECONDS - 60),
+    ]);
+
+    let items = run(
+        &["EUR", "BTC"],
+        vec![price(GRT, "GRT", "0.5", Source::Uniswap)],
+        ChainlinkPrices::default(),
+        &store,
+    );
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].token.as_ref().unwrap().address, GRT);
+    assert_eq!(items[0].quote_currency, "EUR");
+    assert_price_eq(&items[0].price, "0.4");
+    assert_eq!(items[1].quote_currency, "BTC");
+    assert_price_eq(&items[1].price, "0.00001");
+}
+
+#[test]
+fn skips_quote_currencies_without_an_answer() {
+    let store = answers_store(&[("EUR", "1.25", BLOCK_SECONDS - 60)]);
+
+    let items = run(
+        &["EUR", "GBP"],
+        vec![price(GRT, "GRT", "0.5", Source::Uniswap)],
+        ChainlinkPrices::default(),
+        &store,
+    );
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].quote_currency, "EUR");
+}
+
+#[test]
+fn skips_quote_currencies_with_a_zero_answer() {
+    let store = answers_store(&[("EUR", "0", BLOCK_SECONDS - 60)]);
+
+    let items = run(
+        &["EUR"],
+        vec![price(GRT, "GRT", "0.5", Source::Uniswap)],
+        ChainlinkPrices::default(),
+        &store,
+    );
+
+    assert!(items.is_empty());
+}
+
+#[test]
+fn skips_quote_currencies_with_a_stale_answer() {
+    let store = answers_store(&[
+        ("EUR", "1.25", BLOCK_SECONDS - HEARTBEAT_SECONDS - 1),
+        ("BTC", "50000", BLOCK_SECONDS - HEARTBEAT_SECONDS),
+    ]);
+
+    let items = run(
+        &["EUR", "BTC"],
+        vec![price(GRT, "GRT", "0.5", Source::Uniswap)],
+        ChainlinkPrices::default(),
+        &store,
+    );
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].quote_currency, "BTC");
+}
+
+#[test]
+fn converts_chainlink_prices_in_place_of_superseded_uniswap_prices() {
+    let store = answers_store(&[("EUR", "1.25", BLOCK_SECONDS - 60)]);
+    let chainlink_prices = ChainlinkPrices {
+        items: vec![price(GRT, "GRT", "0.75", Source::Chainlink)],
+        chainlink_first: true,
+        chainlink_priced_tokens: vec![GRT.to_string()],
+    };
+
+    let items = run(
+        &["EUR"],
+        vec![
+            price(GRT, "GRT", "0.5", Source::Uniswap),
+            price(DAI, "DAI", "1", Source::Uniswap),
+        ],
+        chainlink_prices,
+        &store,
+    );
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].token.as_ref().unwrap().address, DAI);
+    assert_price_eq(&items[0].price, "0.8");
+    assert_eq!(items[1].token.as_ref().unwrap().address, GRT);
+    assert_price_eq(&items[1].price, "0.6");
+}
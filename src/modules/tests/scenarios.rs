@@ -0,0 +1,160 @@
+use std::{collections::HashSet, fs, path::Path, str::FromStr};
+
+use prost::Message;
+use substreams::scalar::BigDecimal;
+use substreams_ethereum::pb::eth::v2 as eth;
+
+use super::{pipeline::Pipeline, tokens::erc20};
+use crate::{
+    pb::{
+        chainlink::v1::Price,
+        uniswap_pricing::v1::{Erc20Token, PairCreated},
+    },
+    store_key_manager::StoreKey,
+};
+
+// Replays the blocks of every case in `fixtures/scenarios` and checks the prices emitted by
+// `map_uniswap_prices` against the expected ones, see `fixtures/scenarios/README.md`. The blocks
+// are reconstructed, not recorded from mainnet.
+#[test]
+fn scenario_prices() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/scenarios");
+    let mut cases: Vec<_> = fs::read_dir(&fixtures)
+        .expect("missing fixtures/scenarios")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.join("case.txt").exists())
+        .collect();
+    cases.sort();
+    assert!(!cases.is_empty(), "no case in fixtures/scenarios");
+
+    for case in cases {
+        run_case(&case);
+    }
+}
+
+struct Expectation {
+    block_number: u64,
+    token: String,
+    price_usd: BigDecimal,
+    tolerance_percent: BigDecimal,
+}
+
+fn run_case(case: &Path) {
+    let name = case.file_name().unwrap().to_string_lossy().to_string();
+    let definition = fs::read_to_string(case.join("case.txt")).unwrap();
+
+    let mut tokens: Vec<Erc20Token> = Vec::new();
+    let mut pairs: Vec<(String, String, String)> = Vec::new();
+    let mut answers: Vec<(String, String, Price)> = Vec::new();
+    let mut expectations: Vec<Expectation> = Vec::new();
+
+    for line in definition.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            ["token", address, symbol, decimals] => {
+                tokens.push(erc20(address, symbol, decimals.parse().unwrap()))
+            }
+            ["pair", address, token0, token1] => {
+                pairs.push((address.to_string(), token0.to_string(), token1.to_string()))
+            }
+            ["chainlink", base, quote, price, timestamp] => answers.push((
+                base.to_string(),
+                quote.to_string(),
+                Price {
+                    price: price.to_string(),
+                    timestamp: timestamp.parse().unwrap(),
+                    ..Default::default()
+                },
+            )),
+            ["expect", block_number, token, price_usd, tolerance_percent] => {
+                expectations.push(Expectation {
+                    block_number: block_number.parse().unwrap(),
+                    token: token.to_string(),
+                    price_usd: BigDecimal::from_str(price_usd).unwrap(),
+                    tolerance_percent: BigDecimal::from_str(tolerance_percent).unwrap(),
+                })
+            }
+            _ => panic!("{}: invalid case line `{}`", name, line),
+        }
+    }
+
+    let pipeline = Pipeline::new(&tokens);
+    for (address, token0, token1) in pairs {
+        let token = |address: &str| pipeline.tokens.get(address).cloned();
+        let pair = PairCreated {
+            token0: Some(token(&token0).expect("pair token0 is not a case token")),
+            token1: Some(token(&token1).expect("pair token1 is not a case token")),
+            pair_address: address.clone(),
+            ..Default::default()
+        };
+        pipeline
+            .pairs_store
+            .insert(StoreKey::pair_key(&address), pair);
+    }
+    for (base, quote, answer) in answers {
+        pipeline
+            .chainlink_answers_store
+            .insert(StoreKey::chainlink_answer(&base, &quote), answer);
+    }
+
+    let mut blocks: Vec<_> = fs::read_dir(case.join("blocks"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    blocks.sort();
+    assert!(!blocks.is_empty(), "{}: no blocks", name);
+    let mut replayed = HashSet::new();
+
+    for path in blocks {
+        let blk = eth::Block::decode(fs::read(&path).unwrap().as_slice()).unwrap();
+        let output = pipeline.process(&blk);
+        replayed.insert(blk.number);
+
+        for expectation in expectations
+            .iter()
+            .filter(|expectation| expectation.block_number == blk.number)
+        {
+            // The last price of the token in the block is the one the stores keep
+            let price = output
+                .uniswap_prices
+                .items
+                .iter()
+                .filter(|price| price.token_ref().address == expectation.token)
+                .last()
+                .unwrap_or_else(|| {
+                    panic!(
+                        "{}: no price for {} in block {}",
+                        name, expectation.token, blk.number
+                    )
+                });
+
+            let actual = BigDecimal::from_str(&price.price_usd).unwrap();
+            let difference = actual.clone() - expectation.price_usd.clone();
+            let tolerance = expectation.price_usd.clone() * expectation.tolerance_percent.clone()
+                / BigDecimal::from(100);
+            assert!(
+                difference <= tolerance && BigDecimal::zero() - difference <= tolerance,
+                "{}: {} in block {} priced at {}, expected {} ± {}%",
+                name,
+                expectation.token,
+                blk.number,
+                actual,
+                expectation.price_usd,
+                expectation.tolerance_percent
+            );
+        }
+    }
+
+    // An expectation on a block that is not replayed would otherwise never be checked
+    for expectation in &expectations {
+        assert!(
+            replayed.contains(&expectation.block_number),
+            "{}: block {} of an expectation is not in the case",
+            name,
+            expectation.block_number
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use super::{memory_store::MemoryStore, tokens::*};
+use crate::{
+    modules::map_stale_prices::stale_prices,
+    pb::uniswap_pricing::v1::{Erc20Price, StalePrice},
+    store_key_manager::StoreKey,
+};
+
+const BLOCK_SECONDS: i64 = 1_700_000_000;
+const MAX_AGE_SECONDS: i64 = 3600;
+
+fn run(last_updated_store: &MemoryStore<i64>, tokens: &[&str]) -> Vec<StalePrice> {
+    let prices_store: MemoryStore<Erc20Price> = MemoryStore::default();
+    stale_prices(
+        MAX_AGE_SECONDS,
+        tokens,
+        BLOCK_SECONDS,
+        last_updated_store,
+        &prices_store,
+    )
+}
+
+#[test]
+fn reports_prices_older_than_the_max_age() {
+    let last_updated_store = MemoryStore::default();
+    last_updated_store.insert(
+        StoreKey::last_updated_by_address(GRT),
+        BLOCK_SECONDS - MAX_AGE_SECONDS - 1,
+    );
+    last_updated_store.insert(
+        StoreKey::last_updated_by_address(DAI),
+        BLOCK_SECONDS - MAX_AGE_SECONDS,
+    );
+
+    let items = run(&last_updated_store, &[GRT, DAI]);
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].token_address, GRT);
+    assert_eq!(items[0].age_seconds, MAX_AGE_SECONDS as u64 + 1);
+}
+
+#[test]
+fn reports_tokens_that_were_never_priced() {
+    let items = run(&MemoryStore::default(), &[&format!("0x{}", GRT)]);
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].token_address, GRT);
+    assert_eq!(items[0].last_price, None);
+    assert_eq!(items[0].age_seconds, 0);
+}
@@ -0,0 +1,30 @@
+use crate::pb::uniswap_pricing::v1::Erc20Token;
+
+pub const USDC: &str = "a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+pub const DAI: &str = "6b175474e89094c44da98b954eedeac495271d0f";
+pub const WETH: &str = "c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2";
+pub const GRT: &str = "c944e90c64b2c07662a292be6244bdf05cda44a7";
+
+// Synthetic pair addresses, named `<token0>_<token1>`
+pub const USDC_WETH: &str = "00000000000000000000000000000000000000a1";
+pub const DAI_WETH: &str = "00000000000000000000000000000000000000a2";
+pub const WETH_GRT: &str = "00000000000000000000000000000000000000a3";
+pub const USDC_GRT: &str = "00000000000000000000000000000000000000a4";
+
+pub fn erc20(address: &str, symbol: &str, decimals: u64) -> Erc20Token {
+    Erc20Token {
+        address: address.to_string(),
+        name: symbol.to_string(),
+        symbol: symbol.to_string(),
+        decimals,
+    }
+}
+
+pub fn mainnet_tokens() -> Vec<Erc20Token> {
+    vec![
+        erc20(USDC, "USDC", 6),
+        erc20(DAI, "DAI", 18),
+        erc20(WETH, "WETH", 18),
+        erc20(GRT, "GRT", 18),
+    ]
+}
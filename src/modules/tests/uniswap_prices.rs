@@ -0,0 +1,339 @@
+use super::{
+    assert_price_eq,
+    blocks::{units, BlockBuilder},
+    pipeline::Pipeline,
+    tokens::*,
+};
+use crate::{
+    pb::{chainlink::v1::Price, uniswap_pricing::v1::erc20_price::Source},
+    store_key_manager::StoreKey,
+};
+
+const BLOCK_NUMBER: u64 = 10008355;
+const BLOCK_TIMESTAMP: i64 = 1588710145;
+
+// 2,000 USDC per WETH
+fn usdc_weth_block() -> BlockBuilder {
+    BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+        .pair_created(USDC, WETH, USDC_WETH)
+        .sync(USDC_WETH, units(2_000_000, 6), units(1_000, 18))
+}
+
+#[test]
+fn stores_created_pairs_with_their_tokens() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    pipeline.process(&usdc_weth_block().build());
+
+    let pair = pipeline
+        .pairs_store
+        .value(&StoreKey::pair_key(USDC_WETH))
+        .expect("pair not stored");
+    assert_eq!(pair.token0_ref().symbol, "USDC");
+    assert_eq!(pair.token0_ref().decimals, 6);
+    assert_eq!(pair.token1_ref().symbol, "WETH");
+    assert_eq!(pair.block_number, BLOCK_NUMBER);
+}
+
+#[test]
+fn skips_pairs_with_unresolved_tokens() {
+    let pipeline = Pipeline::new(&[erc20(WETH, "WETH", 18)]);
+    let output = pipeline.process(&usdc_weth_block().build());
+
+    assert_eq!(
+        pipeline.pairs_store.value(&StoreKey::pair_key(USDC_WETH)),
+        None
+    );
+    assert!(output.weth_prices.items.is_empty());
+    assert!(output.uniswap_prices.items.is_empty());
+}
+
+#[test]
+fn prices_weth_from_a_stablecoin_pair() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    let output = pipeline.process(&usdc_weth_block().build());
+
+    assert_eq!(output.weth_prices.items.len(), 1);
+    let weth_price = &output.weth_prices.items[0];
+    assert_eq!(weth_price.token_ref().address, WETH);
+    assert_price_eq(&weth_price.price_usd, "2000");
+    assert_eq!(weth_price.pair_address, USDC_WETH);
+
+    let stored = pipeline
+        .weth_price_store
+        .value(&StoreKey::eth_usd_price_key())
+        .expect("ETH price not stored");
+    assert_price_eq(&stored.price_usd, "2000");
+}
+
+#[test]
+fn prices_both_sides_of_a_stablecoin_weth_pair() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    let output = pipeline.process(&usdc_weth_block().build());
+
+    assert_eq!(output.uniswap_prices.items.len(), 2);
+
+    let weth = output.price_of(WETH);
+    assert_price_eq(&weth.price_usd, "2000");
+    assert_price_eq(&weth.price_eth, "1");
+
+    let usdc = output.price_of(USDC);
+    assert_price_eq(&usdc.price_usd, "1");
+    assert_price_eq(&usdc.price_eth, "0.0005");
+    assert_eq!(usdc.eth_price_source, Source::Uniswap as i32);
+}
+
+#[test]
+fn prices_tokens_against_weth_with_the_uniswap_eth_price() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    pipeline.process(&usdc_weth_block().build());
+
+    let output = pipeline.process(
+        &BlockBuilder::new(BLOCK_NUMBER + 1, BLOCK_TIMESTAMP + 13)
+            .pair_created(WETH, GRT, WETH_GRT)
+            .sync(WETH_GRT, units(10, 18), units(100_000, 18))
+            .build(),
+    );
+
+    let grt = output.price_of(GRT);
+    assert_price_eq(&grt.price_usd, "0.2");
+    assert_price_eq(&grt.price_eth, "0.0001");
+    assert_eq!(grt.source, Source::Uniswap as i32);
+    assert_eq!(grt.eth_price_source, Source::Uniswap as i32);
+    assert_eq!(grt.pair_address, WETH_GRT);
+    assert_eq!(grt.block_number, BLOCK_NUMBER + 1);
+}
+
+#[test]
+fn prices_tokens_in_eth_without_an_eth_price() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    let output = pipeline.process(
+        &BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+            .pair_created(WETH, GRT, WETH_GRT)
+            .sync(WETH_GRT, units(10, 18), units(100_000, 18))
+            .build(),
+    );
+
+    let grt = output.price_of(GRT);
+    assert_eq!(grt.price_usd, "");
+    assert_price_eq(&grt.price_eth, "0.0001");
+    assert_eq!(grt.eth_price_source, Source::Unknown as i32);
+}
+
+#[test]
+fn prices_tokens_against_a_stablecoin_across_decimals() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    let output = pipeline.process(
+        &BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+            .pair_created(USDC, GRT, USDC_GRT)
+            .sync(USDC_GRT, units(500, 6), units(1_000, 18))
+            .build(),
+    );
+
+    let grt = output.price_of(GRT);
+    assert_price_eq(&grt.price_usd, "0.5");
+    // No ETH price is known yet
+    assert_eq!(grt.price_eth, "");
+    assert_eq!(grt.eth_price_source, Source::Unknown as i32);
+}
+
+#[test]
+fn prefers_a_fresh_chainlink_eth_price() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    pipeline.chainlink_answers_store.insert(
+        StoreKey::chainlink_answer("ETH", "USD"),
+        Price {
+            price: String::from("2500"),
+            timestamp: BLOCK_TIMESTAMP - 60,
+            ..Default::default()
+        },
+    );
+
+    let output = pipeline.process(
+        &usdc_weth_block()
+            .pair_created(WETH, GRT, WETH_GRT)
+            .sync(WETH_GRT, units(10, 18), units(100_000, 18))
+            .build(),
+    );
+
+    let grt = output.price_of(GRT);
+    assert_price_eq(&grt.price_usd, "0.25");
+    assert_eq!(grt.eth_price_source, Source::Chainlink as i32);
+}
+
+#[test]
+fn falls_back_to_the_uniswap_eth_price_when_chainlink_is_stale() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    pipeline.chainlink_answers_store.insert(
+        StoreKey::chainlink_answer("ETH", "USD"),
+        Price {
+            price: String::from("2500"),
+            timestamp: BLOCK_TIMESTAMP - 3601,
+            ..Default::default()
+        },
+    );
+
+    let output = pipeline.process(
+        &usdc_weth_block()
+            .pair_created(WETH, GRT, WETH_GRT)
+            .sync(WETH_GRT, units(10, 18), units(100_000, 18))
+            .build(),
+    );
+
+    let grt = output.price_of(GRT);
+    assert_price_eq(&grt.price_usd, "0.2");
+    assert_eq!(grt.eth_price_source, Source::Uniswap as i32);
+}
+
+#[test]
+fn values_stablecoins_at_their_chainlink_price() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    pipeline.chainlink_answers_store.insert(
+        StoreKey::chainlink_answer("USDC", "USD"),
+        Price {
+            price: String::from("0.99"),
+            timestamp: BLOCK_TIMESTAMP - 60,
+            ..Default::default()
+        },
+    );
+
+    let output = pipeline.process(&usdc_weth_block().build());
+
+    assert_price_eq(&output.weth_prices.items[0].price_usd, "1980");
+    assert_price_eq(&output.price_of(WETH).price_usd, "1980");
+}
+
+#[test]
+fn values_stablecoins_at_their_peg_when_chainlink_is_stale() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    pipeline.chainlink_answers_store.insert(
+        StoreKey::chainlink_answer("USDC", "USD"),
+        Price {
+            price: String::from("0.99"),
+            timestamp: BLOCK_TIMESTAMP - 86401,
+            ..Default::default()
+        },
+    );
+
+    let output = pipeline.process(&usdc_weth_block().build());
+
+    assert_price_eq(&output.weth_prices.items[0].price_usd, "2000");
+}
+
+#[test]
+fn prices_weth_at_the_eth_price_once_per_block() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    pipeline.chainlink_answers_store.insert(
+        StoreKey::chainlink_answer("ETH", "USD"),
+        Price {
+            price: String::from("2500"),
+            timestamp: BLOCK_TIMESTAMP - 60,
+            ..Default::default()
+        },
+    );
+
+    let output = pipeline.process(
+        &usdc_weth_block()
+            .pair_created(WETH, GRT, WETH_GRT)
+            .sync(WETH_GRT, units(10, 18), units(100_000, 18))
+            .build(),
+    );
+
+    // Valued at the price GRT was valued with, not at the USDC/WETH ratio
+    let weth = output.price_of(WETH);
+    assert_price_eq(&weth.price_usd, "2500");
+    assert_price_eq(&weth.price_eth, "1");
+    assert_eq!(weth.eth_price_source, Source::Chainlink as i32);
+    assert_eq!(weth.source, Source::Reference as i32);
+    assert_eq!(weth.pair_address, "");
+}
+
+#[test]
+fn prices_stablecoins_outside_of_weth_pairs() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    pipeline.chainlink_answers_store.insert(
+        StoreKey::chainlink_answer("USDC", "USD"),
+        Price {
+            price: String::from("0.99"),
+            timestamp: BLOCK_TIMESTAMP - 60,
+            ..Default::default()
+        },
+    );
+
+    let output = pipeline.process(
+        &BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+            .pair_created(USDC, GRT, USDC_GRT)
+            .sync(USDC_GRT, units(500, 6), units(1_000, 18))
+            .build(),
+    );
+
+    let usdc = output.price_of(USDC);
+    assert_price_eq(&usdc.price_usd, "0.99");
+    assert_eq!(usdc.source, Source::Reference as i32);
+    assert_eq!(usdc.pair_address, "");
+    assert_price_eq(&output.price_of(GRT).price_usd, "0.495");
+}
+
+#[test]
+fn keeps_the_pool_price_of_a_depegged_stablecoin() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    for (base, price) in [("ETH", "1450"), ("USDC", "0.88")] {
+        pipeline.chainlink_answers_store.insert(
+            StoreKey::chainlink_answer(base, "USD"),
+            Price {
+                price: String::from(price),
+                timestamp: BLOCK_TIMESTAMP - 60,
+                ..Default::default()
+            },
+        );
+    }
+
+    let output = pipeline.process(
+        &BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+            .pair_created(USDC, GRT, USDC_GRT)
+            .sync(USDC_GRT, units(500, 6), units(1_000, 18))
+            .pair_created(USDC, WETH, USDC_WETH)
+            .sync(USDC_WETH, units(1_600_000, 6), units(1_000, 18))
+            .build(),
+    );
+
+    // The reference price stands in until the USDC/WETH pool prices USDC in the same block
+    let usdc_prices: Vec<_> = output
+        .uniswap_prices
+        .items
+        .iter()
+        .filter(|price| price.token_ref().address == USDC)
+        .collect();
+    assert_eq!(usdc_prices.len(), 2);
+    assert_eq!(usdc_prices[0].source, Source::Reference as i32);
+    assert_price_eq(&usdc_prices[0].price_usd, "0.88");
+
+    assert_eq!(usdc_prices[1].source, Source::Uniswap as i32);
+    assert_eq!(usdc_prices[1].pair_address, USDC_WETH);
+    assert_price_eq(&usdc_prices[1].price_usd, "0.90625");
+}
+
+#[test]
+fn ignores_sync_events_of_unknown_pairs() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    let output = pipeline.process(
+        &BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+            .sync(DAI_WETH, units(2_000_000, 18), units(1_000, 18))
+            .build(),
+    );
+
+    assert!(output.weth_prices.items.is_empty());
+    assert!(output.uniswap_prices.items.is_empty());
+}
+
+#[test]
+fn ignores_pairs_with_an_empty_reserve() {
+    let pipeline = Pipeline::new(&mainnet_tokens());
+    let output = pipeline.process(
+        &BlockBuilder::new(BLOCK_NUMBER, BLOCK_TIMESTAMP)
+            .pair_created(USDC, GRT, USDC_GRT)
+            .sync(USDC_GRT, 0, units(1_000, 18))
+            .build(),
+    );
+
+    assert!(output.uniswap_prices.items.is_empty());
+}
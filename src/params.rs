@@ -0,0 +1,49 @@
+use std::{collections::HashMap, str::FromStr};
+
+use substreams::errors::Error;
+
+// Module params are passed in from the manifest as `key=value` pairs joined by `&`,
+// e.g. `max_age_seconds=86400&tokens=<address>,<address>`.
+pub struct Params<'a> {
+    values: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> Params<'a> {
+    pub fn parse(params: &'a str) -> Self {
+        Params {
+            values: params
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        self.values
+            .get(key)
+            .copied()
+            .filter(|value| !value.is_empty())
+    }
+
+    pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, Error> {
+        match self.get(key) {
+            Some(value) => value.parse::<T>().map_err(|_| {
+                Error::Unexpected(format!("invalid value for param `{}`: {}", key, value))
+            }),
+            None => Ok(default),
+        }
+    }
+
+    pub fn get_list(&self, key: &str) -> Vec<&'a str> {
+        self.get(key)
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
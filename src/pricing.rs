@@ -0,0 +1,204 @@
+use std::str::FromStr;
+
+use substreams::scalar::{BigDecimal, BigInt};
+
+use crate::constants::WETH_ADDRESS_BYTES;
+
+pub type Address = [u8; 20];
+
+// Everything needed to price the tokens of a Uniswap V2 pair from its reserves. Handlers resolve
+// the base token and ETH prices from their stores, the pricing itself is pure.
+pub struct PricingInput {
+    pub token0: Address,
+    pub token1: Address,
+    // Raw reserves, in each token's smallest unit
+    pub reserve0: BigInt,
+    pub reserve1: BigInt,
+    pub decimals0: u64,
+    pub decimals1: u64,
+    // USD price of a token that can be used to price the other side of the pair, e.g. a
+    // stablecoin, `None` otherwise
+    pub base_price0: Option<BigDecimal>,
+    pub base_price1: Option<BigDecimal>,
+    // ETH price in USD, `None` when it is not known
+    pub eth_price: Option<BigDecimal>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Token0,
+    Token1,
+}
+
+impl Side {
+    pub fn select<T>(self, token0: T, token1: T) -> T {
+        match self {
+            Side::Token0 => token0,
+            Side::Token1 => token1,
+        }
+    }
+}
+
+// What the priced token was valued against
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Anchor {
+    BaseToken,
+    Weth,
+    // Valued at its reference price rather than from the pool, see `price_pair_with_anchors`
+    Reference,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PricedToken {
+    pub side: Side,
+    // `None` when priced against WETH and the ETH price is not known
+    pub price_usd: Option<BigDecimal>,
+    // `None` when priced against a base token and the ETH price is not known
+    pub price_eth: Option<BigDecimal>,
+    pub anchor: Anchor,
+}
+
+// Prices each side of the pair that is paired against a base token or WETH. A pair of two
+// anchors, e.g. USDC/WETH, prices both sides. Pairs with an empty reserve price nothing. Without an
+// ETH price, the sides priced against WETH still get their `price_eth` from the reserves.
+pub fn price_pair(input: PricingInput) -> Vec<PricedToken> {
+    let reserve0 = input.reserve0.to_decimal(input.decimals0);
+    let reserve1 = input.reserve1.to_decimal(input.decimals1);
+    if reserve0 == BigDecimal::zero() || reserve1 == BigDecimal::zero() {
+        return Vec::new();
+    }
+    let eth_price = input
+        .eth_price
+        .filter(|eth_price| eth_price != &BigDecimal::zero());
+
+    let mut prices = Vec::new();
+
+    if let Some(base_price) = input.base_price0 {
+        let price_usd = reserve0.clone() / reserve1.clone() * base_price;
+        prices.push(priced_against_base(Side::Token1, price_usd, &eth_price));
+    }
+    if let Some(base_price) = input.base_price1 {
+        let price_usd = reserve1.clone() / reserve0.clone() * base_price;
+        prices.push(priced_against_base(Side::Token0, price_usd, &eth_price));
+    }
+    if input.token0 == WETH_ADDRESS_BYTES {
+        let price_eth = reserve0.clone() / reserve1.clone();
+        prices.push(priced_against_weth(Side::Token1, price_eth, &eth_price));
+    }
+    if input.token1 == WETH_ADDRESS_BYTES {
+        let price_eth = reserve1 / reserve0;
+        prices.push(priced_against_weth(Side::Token0, price_eth, &eth_price));
+    }
+
+    prices
+}
+
+// Prices every side of the pair for the main price store. WETH anchors the other tokens and is
+// valued at its reference price, the ETH price, rather than against the other side, so it is
+// stored with the price every other token was valued with. A base token is priced from the pool
+// like any other token, so a depeg shows, and only falls back to its reference price, its base
+// price, when the pool gives it no USD price.
+pub fn price_pair_with_anchors(input: PricingInput) -> Vec<PricedToken> {
+    if input.reserve0 == BigInt::zero() || input.reserve1 == BigInt::zero() {
+        return Vec::new();
+    }
+    let eth_price = input
+        .eth_price
+        .clone()
+        .filter(|eth_price| eth_price != &BigDecimal::zero());
+    let (token0, token1) = (input.token0, input.token1);
+    let sides = [
+        (Side::Token0, input.base_price0.clone()),
+        (Side::Token1, input.base_price1.clone()),
+    ];
+    let is_weth_anchor =
+        |side: Side| side.select(token0, token1) == WETH_ADDRESS_BYTES && eth_price.is_some();
+    let is_base_token = |side: Side| {
+        sides
+            .iter()
+            .any(|(base_side, base_price)| *base_side == side && base_price.is_some())
+    };
+
+    let mut prices: Vec<PricedToken> = price_pair(input)
+        .into_iter()
+        .filter(|price| {
+            !is_weth_anchor(price.side) && (!is_base_token(price.side) || price.price_usd.is_some())
+        })
+        .collect();
+
+    for (side, base_price) in sides {
+        if prices.iter().any(|price| price.side == side) {
+            continue;
+        }
+        let reference = match base_price {
+            Some(base_price) => Some(priced_against_base(side, base_price, &eth_price)),
+            None if side.select(token0, token1) == WETH_ADDRESS_BYTES => eth_price
+                .as_ref()
+                .map(|_| priced_against_weth(side, BigDecimal::one(), &eth_price)),
+            None => None,
+        };
+        prices.extend(reference.map(|price| PricedToken {
+            anchor: Anchor::Reference,
+            ..price
+        }));
+    }
+
+    prices
+}
+
+fn priced_against_base(
+    side: Side,
+    price_usd: BigDecimal,
+    eth_price: &Option<BigDecimal>,
+) -> PricedToken {
+    PricedToken {
+        side,
+        price_eth: eth_price
+            .as_ref()
+            .map(|eth_price| price_usd.clone() / eth_price.clone()),
+        price_usd: Some(price_usd),
+        anchor: Anchor::BaseToken,
+    }
+}
+
+fn priced_against_weth(
+    side: Side,
+    price_eth: BigDecimal,
+    eth_price: &Option<BigDecimal>,
+) -> PricedToken {
+    PricedToken {
+        side,
+        price_usd: eth_price
+            .as_ref()
+            .map(|eth_price| price_eth.clone() * eth_price.clone()),
+        price_eth: Some(price_eth),
+        anchor: Anchor::Weth,
+    }
+}
+
+// Price moves, in percent, depth is measured for
+pub const DEPTH_PERCENTS: [u32; 3] = [1, 2, 5];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Depth {
+    pub percent: u32,
+    // USD value of token1 bought token0 with until token0's price, in token1, is `percent` higher
+    pub up_usd: BigDecimal,
+    // USD value of token0 sold until token0's price, in token1, is `percent` lower
+    pub down_usd: BigDecimal,
+}
+
+// Depth of a constant product pair whose reserves are each worth `reserve_usd`, excluding the
+// swap fee. Moving the price of token0 by a factor `m` moves its reserve by `1 / sqrt(m)`, so
+// buying it up takes `sqrt(1 + p) - 1` times the token1 reserve and selling it down takes
+// `1 / sqrt(1 - p) - 1` times the token0 reserve. `percent` must be below 100.
+pub fn depth(reserve_usd: &BigDecimal, percent: u32) -> Depth {
+    let move_fraction = f64::from(percent) / 100.0;
+    let factor = |value: f64| BigDecimal::from_str(&value.to_string()).unwrap();
+
+    Depth {
+        percent,
+        up_usd: reserve_usd.clone() * factor((1.0 + move_fraction).sqrt() - 1.0),
+        down_usd: reserve_usd.clone() * factor(1.0 / (1.0 - move_fraction).sqrt() - 1.0),
+    }
+}
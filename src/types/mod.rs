@@ -1 +1,3 @@
-pub mod token;
\ No newline at end of file
+pub mod pricing_mode;
+pub mod reserve_change;
+pub mod stable_coin;
@@ -0,0 +1,23 @@
+use substreams::errors::Error;
+
+use crate::params::Params;
+
+// Which source takes precedence when pricing a token.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PricingMode {
+    // Every price is derived from Uniswap reserves.
+    Uniswap,
+    // Tokens with a Chainlink USD feed are priced from Chainlink, Uniswap prices the long tail.
+    ChainlinkFirst,
+}
+
+impl PricingMode {
+    // Reads the `pricing_mode` param (`uniswap` or `chainlink_first`), defaulting to `uniswap`.
+    pub fn from_params(params: &Params) -> Result<Self, Error> {
+        match params.get("pricing_mode") {
+            None | Some("uniswap") => Ok(PricingMode::Uniswap),
+            Some("chainlink_first") => Ok(PricingMode::ChainlinkFirst),
+            Some(mode) => Err(Error::Unexpected(format!("unknown pricing_mode: {}", mode))),
+        }
+    }
+}
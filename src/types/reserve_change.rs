@@ -0,0 +1,89 @@
+use std::{collections::HashMap, str::FromStr};
+
+use substreams::{
+    pb::substreams::store_delta::Operation,
+    scalar::{BigDecimal, BigInt},
+    store::DeltaProto,
+};
+
+use crate::{pb::uniswap_pricing::v1::ReserveSnapshot, store_key_manager::StoreKey};
+
+// Default length of the windows liquidity drops are measured over, about five minutes on mainnet.
+pub const DEFAULT_WINDOW_BLOCKS: u64 = 25;
+
+// Windows are laid out on two grids, the second offset by half a window, so any change spread over
+// at most half a window falls within a single window of one of them.
+pub const WINDOW_GRIDS: [u32; 2] = [0, 1];
+
+// Reserves of a pair before and after a block, read from the deltas of `store_pair_reserves`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReserveChange {
+    // Whether the pair got its first reserves in the block, `before` then being empty
+    pub created: bool,
+    pub before: ReserveSnapshot,
+    pub after: ReserveSnapshot,
+    // LP tokens minted minus burnt by the pair over the block
+    pub lp_supply_change: BigInt,
+}
+
+impl ReserveChange {
+    // One change per pair updated in the block, in the order the pairs were first updated
+    pub fn from_deltas(deltas: impl IntoIterator<Item = DeltaProto<ReserveSnapshot>>) -> Vec<Self> {
+        let mut changes: Vec<ReserveChange> = Vec::new();
+        let mut indexes: HashMap<String, usize> = HashMap::new();
+
+        for delta in deltas {
+            // Every snapshot is also stored under its token addresses
+            if delta.key != StoreKey::pair_reserves(&delta.new_value.pair_address) {
+                continue;
+            }
+            let lp_supply_change = BigInt::from_str(&delta.new_value.lp_supply_change)
+                .unwrap_or_else(|_| BigInt::zero());
+
+            match indexes.get(&delta.key) {
+                Some(index) => {
+                    let change = &mut changes[*index];
+                    change.lp_supply_change = change.lp_supply_change.clone() + lp_supply_change;
+                    change.after = delta.new_value;
+                }
+                None => {
+                    indexes.insert(delta.key, changes.len());
+                    changes.push(ReserveChange {
+                        created: delta.operation == Operation::Create,
+                        before: delta.old_value,
+                        after: delta.new_value,
+                        lp_supply_change,
+                    });
+                }
+            }
+        }
+
+        changes
+    }
+}
+
+// Reserves of a snapshot in whole tokens
+pub fn reserves(snapshot: &ReserveSnapshot) -> (BigDecimal, BigDecimal) {
+    let reserve = |value: &str, decimals: u64| {
+        BigInt::from_str(value)
+            .unwrap_or_else(|_| BigInt::zero())
+            .to_decimal(decimals)
+    };
+
+    (
+        reserve(&snapshot.reserve0, snapshot.token0_ref().decimals),
+        reserve(&snapshot.reserve1, snapshot.token1_ref().decimals),
+    )
+}
+
+// Product of the reserves in whole tokens, which swaps never lower and only removing liquidity
+// does
+pub fn constant_product(snapshot: &ReserveSnapshot) -> BigDecimal {
+    let (reserve0, reserve1) = reserves(snapshot);
+    reserve0 * reserve1
+}
+
+// Index of the window of `window_blocks` blocks holding `block_number` on `grid`
+pub fn liquidity_window(block_number: u64, window_blocks: u64, grid: u32) -> u64 {
+    (block_number + u64::from(grid) * (window_blocks / 2)) / window_blocks
+}
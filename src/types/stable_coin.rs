@@ -0,0 +1,159 @@
+use std::{collections::HashMap, str::FromStr};
+
+use substreams::{errors::Error, scalar::BigDecimal, store::StoreGet};
+
+use crate::{
+    chainlink_feeds::fresh_usd_answer,
+    constants::DEFAULT_STABLE_COINS,
+    params::Params,
+    pb::{
+        chainlink::v1::Price,
+        uniswap_pricing::v1::{StableCoinPrice, StableCoinPrices},
+    },
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StableCoin {
+    pub address: String,
+    // Currency the coin is pegged to, e.g. USD or EUR
+    pub peg: String,
+    // Algorithmic coins have no reserve backing their peg, so they are only trusted as a pricing
+    // anchor when a Chainlink feed confirms their value
+    pub algorithmic: bool,
+    // Base symbol of the coin's `<feed>/USD` Chainlink feed, if it has one
+    pub chainlink_feed: Option<String>,
+}
+
+impl StableCoin {
+    pub fn new(address: &str, peg: &str, algorithmic: bool, chainlink_feed: Option<&str>) -> Self {
+        StableCoin {
+            address: address.to_string(),
+            peg: peg.to_string(),
+            algorithmic,
+            chainlink_feed: chainlink_feed.map(str::to_string),
+        }
+    }
+
+    // Parses a `<address>:<peg>:<algorithmic>[:<chainlink feed>]` param entry,
+    // e.g. `a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48:USD:false:USDC`
+    pub fn parse(entry: &str) -> Result<Self, Error> {
+        let fields: Vec<&str> = entry.split(':').map(str::trim).collect();
+        let invalid = || Error::Unexpected(format!("invalid stablecoin entry: {}", entry));
+
+        if fields.len() < 3 || fields.len() > 4 {
+            return Err(invalid());
+        }
+
+        Ok(StableCoin::new(
+            &fields[0].trim_start_matches("0x").to_lowercase(),
+            &fields[1].to_uppercase(),
+            fields[2].parse::<bool>().map_err(|_| invalid())?,
+            fields.get(3).filter(|feed| !feed.is_empty()).copied(),
+        ))
+    }
+
+    // USD value of one coin, or `None` when it should not be used to price other tokens. Chainlink
+    // answers older than `heartbeat_seconds` at `block_seconds` are ignored.
+    pub fn usd_price<S: StoreGet<Price>>(
+        &self,
+        chainlink_answers_store: &S,
+        block_seconds: i64,
+        heartbeat_seconds: i64,
+    ) -> Option<BigDecimal> {
+        let fresh_answer = |base: &str| {
+            fresh_usd_answer(
+                chainlink_answers_store,
+                base,
+                block_seconds,
+                heartbeat_seconds,
+            )
+        };
+
+        if let Some(price) = self.chainlink_feed.as_deref().and_then(fresh_answer) {
+            return Some(price);
+        }
+
+        if self.algorithmic {
+            None
+        } else if self.peg == "USD" {
+            Some(BigDecimal::one())
+        } else {
+            fresh_answer(&self.peg)
+        }
+    }
+}
+
+pub struct StableCoins(Vec<StableCoin>);
+
+impl StableCoins {
+    // Reads the `stablecoins` param, falling back to `DEFAULT_STABLE_COINS` when it is not set.
+    pub fn from_params(params: &Params) -> Result<Self, Error> {
+        let entries = params.get_list("stablecoins");
+        if entries.is_empty() {
+            return Ok(StableCoins(DEFAULT_STABLE_COINS.clone()));
+        }
+
+        entries
+            .into_iter()
+            .map(StableCoin::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map(StableCoins)
+    }
+
+    // Every coin with its USD value at the end of the block, see `StableCoin::usd_price`
+    pub fn prices<S: StoreGet<Price>>(
+        &self,
+        chainlink_answers_store: &S,
+        block_seconds: i64,
+        heartbeat_seconds: i64,
+    ) -> StableCoinPrices {
+        StableCoinPrices {
+            items: self
+                .0
+                .iter()
+                .map(|stable_coin| StableCoinPrice {
+                    address: stable_coin.address.clone(),
+                    price_usd: stable_coin
+                        .usd_price(chainlink_answers_store, block_seconds, heartbeat_seconds)
+                        .map_or_else(String::new, |price| price.to_string()),
+                })
+                .collect(),
+        }
+    }
+}
+
+// The stablecoins resolved by `map_stable_coins`, which every module pricing against stablecoins
+// takes as input instead of parsing the set itself.
+#[derive(Default)]
+pub struct StablePrices(HashMap<String, Option<BigDecimal>>);
+
+impl StablePrices {
+    pub fn from_proto(prices: &StableCoinPrices) -> Self {
+        StablePrices(
+            prices
+                .items
+                .iter()
+                .map(|price| {
+                    (
+                        price.address.clone(),
+                        BigDecimal::from_str(&price.price_usd).ok(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Option<BigDecimal>)> {
+        self.0.iter()
+    }
+
+    pub fn contains(&self, address: &str) -> bool {
+        self.0.contains_key(address)
+    }
+
+    // USD value of the stablecoin at `address`, `None` for any other token or when the coin
+    // should not be used to price other tokens
+    pub fn usd_price(&self, address: &str) -> Option<BigDecimal> {
+        self.0.get(address).cloned().flatten()
+    }
+}